@@ -127,6 +127,56 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         })
     });
 
+    c.bench_function("Grid 10240×10240 at 0°", |b| {
+        b.iter(|| {
+            const WIDTH: f64 = 10240.0;
+            const HEIGHT: f64 = 10240.0;
+            const ANGLE: f64 = 0.0;
+
+            let grid = GridPositionIterator::new(
+                WIDTH as _,
+                HEIGHT as _,
+                7.0,
+                7.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(ANGLE),
+            );
+
+            let mut count = 0;
+            for _ in grid.into_iter() {
+                count += 1;
+            }
+
+            count
+        })
+    });
+
+    c.bench_function("Grid 10240×10240 at 0.01°", |b| {
+        b.iter(|| {
+            const WIDTH: f64 = 10240.0;
+            const HEIGHT: f64 = 10240.0;
+            const ANGLE: f64 = 0.01;
+
+            let grid = GridPositionIterator::new(
+                WIDTH as _,
+                HEIGHT as _,
+                7.0,
+                7.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(ANGLE),
+            );
+
+            let mut count = 0;
+            for _ in grid.into_iter() {
+                count += 1;
+            }
+
+            count
+        })
+    });
+
     c.bench_function("Grid 10240×10240 at 45°", |b| {
         b.iter(|| {
             const WIDTH: f64 = 10240.0;
@@ -151,6 +201,39 @@ pub fn criterion_benchmark(c: &mut Criterion) {
             count
         })
     });
+
+    #[cfg(feature = "simd")]
+    {
+        c.bench_function("Collect 10240×10240 at 45° (scalar)", |b| {
+            b.iter(|| {
+                let grid = GridPositionIterator::new(
+                    10240.0,
+                    10240.0,
+                    7.0,
+                    7.0,
+                    0.0,
+                    0.0,
+                    Angle::<f64>::from_degrees(45.0),
+                );
+                grid.collect::<Vec<_>>()
+            })
+        });
+
+        c.bench_function("Collect 10240×10240 at 45° (SIMD)", |b| {
+            b.iter(|| {
+                let grid = GridPositionIterator::new(
+                    10240.0,
+                    10240.0,
+                    7.0,
+                    7.0,
+                    0.0,
+                    0.0,
+                    Angle::<f64>::from_degrees(45.0),
+                );
+                grid.collect_simd()
+            })
+        });
+    }
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -1,4 +1,4 @@
-use std::ops::Neg;
+use std::ops::{Add, Neg, Sub};
 
 /// An angle expressed in radians.
 #[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
@@ -17,9 +17,6 @@ impl<T> Angle<T> {
 }
 
 pub trait AngleOps<T> {
-    /// Determines the sine and cosine of the angle.
-    fn sin_cos(&self) -> (T, T);
-
     /// Normalizes the specified angle such that it falls into range -PI/2..PI/2.
     fn normalize(&self) -> Self;
 }
@@ -30,18 +27,86 @@ impl Angle<f64> {
         Self(radians.to_radians())
     }
 
+    /// Converts the value into degrees.
+    pub fn degrees(&self) -> f64 {
+        self.0.to_degrees()
+    }
+
     /// Determines the sine and cosine of the angle.
     pub fn sin_cos(&self) -> (f64, f64) {
         self.0.sin_cos()
     }
+
+    /// Normalizes the orientation of a grid like [`AngleOps::normalize`], but keeps
+    /// exact quarter turns rather than reducing them to 0°, such that a grid at 90°
+    /// matches the unrotated grid with swapped dimensions rather than the unrotated grid.
+    pub(crate) fn normalize_grid(&self) -> Self {
+        if self.0.abs() == std::f64::consts::FRAC_PI_2 {
+            *self
+        } else {
+            self.normalize()
+        }
+    }
+
+    /// Determines the sine and cosine of the angle, snapping them to exact values at
+    /// quarter turns, e.g. such that the cosine of 90° is exactly `0` rather than `6.1e-17`.
+    pub(crate) fn sin_cos_snapped(&self) -> (f64, f64) {
+        const EPSILON: f64 = 1e-12;
+        let (sin, cos) = self.sin_cos();
+        if sin.abs() < EPSILON {
+            (0.0, cos.signum())
+        } else if cos.abs() < EPSILON {
+            (sin.signum(), 0.0)
+        } else {
+            (sin, cos)
+        }
+    }
+
+    /// Determines whether two angles differ by no more than the tolerance,
+    /// taking wraparound into account, e.g. 359° and 1° differ by 2°.
+    pub fn approx_eq(&self, other: &Angle, tolerance: Angle) -> bool {
+        use std::f64::consts::{PI, TAU};
+        let mut delta = (self.0 - other.0).rem_euclid(TAU);
+        if delta > PI {
+            delta -= TAU;
+        }
+        delta.abs() <= tolerance.0
+    }
+
+    /// Constructs the value from a 2×2 rotation matrix given in row-major order.
+    ///
+    /// ## Panics
+    /// Panics if the matrix is not (approximately) a pure rotation, i.e. if it
+    /// is not orthonormal or its determinant is not `1`.
+    pub fn from_matrix(m00: f64, m01: f64, m10: f64, m11: f64) -> Self {
+        const EPSILON: f64 = 1e-6;
+        assert!(
+            (m00 - m11).abs() < EPSILON && (m01 + m10).abs() < EPSILON,
+            "matrix is not a pure rotation"
+        );
+        assert!(
+            (m00 * m11 - m01 * m10 - 1.0).abs() < EPSILON,
+            "matrix is not a pure rotation"
+        );
+        Self(m10.atan2(m00))
+    }
+
+    /// Converts the angle into a 2×2 rotation matrix in row-major order,
+    /// i.e. `[m00, m01, m10, m11]`.
+    pub fn to_matrix(&self) -> [f64; 4] {
+        let (sin, cos) = self.sin_cos();
+        [cos, -sin, sin, cos]
+    }
 }
 
-impl AngleOps<f64> for Angle<f64> {
-    /// Determines the sine and cosine of the angle.
-    fn sin_cos(&self) -> (f64, f64) {
-        self.0.sin_cos()
+impl From<f64> for Angle<f64> {
+    /// Constructs the value from an angle specified in radians.
+    fn from(radians: f64) -> Self {
+        Self::from_radians(radians)
     }
+}
 
+impl AngleOps<f64> for Angle<f64> {
     /// Normalizes the specified angle such that it falls into range -PI/2..PI/2.
     fn normalize(&self) -> Self {
         use std::f64::consts::PI;
@@ -63,6 +128,140 @@ impl AngleOps<f64> for Angle<f64> {
     }
 }
 
+/// An angle along with its precomputed sine and cosine.
+///
+/// This avoids recomputing the sine and cosine when rotating many vectors by the same angle.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CachedAngle {
+    angle: Angle<f64>,
+    sin: f64,
+    cos: f64,
+}
+
+impl CachedAngle {
+    /// Precomputes the sine and cosine of the specified angle.
+    pub fn new(angle: Angle<f64>) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self { angle, sin, cos }
+    }
+
+    /// Returns the angle.
+    #[inline(always)]
+    pub const fn angle(&self) -> Angle<f64> {
+        self.angle
+    }
+
+    /// Returns the precomputed sine and cosine of the angle.
+    #[inline(always)]
+    pub const fn sin_cos(&self) -> (f64, f64) {
+        (self.sin, self.cos)
+    }
+}
+
+impl From<Angle<f64>> for CachedAngle {
+    fn from(value: Angle<f64>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Angle<f64> {
+    /// Precomputes the sine and cosine of the angle.
+    pub fn precompute(self) -> CachedAngle {
+        CachedAngle::new(self)
+    }
+}
+
+/// An angle represented by its unit direction, i.e. its sine and cosine, rather than radians.
+///
+/// Angles are added and subtracted by complex multiplication of their directions, i.e.
+/// without trigonometric functions or conversions between degrees and radians, such that
+/// chaining many rotations does not accumulate the errors of these conversions.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct UnitVectorAngle {
+    sin: f64,
+    cos: f64,
+}
+
+impl UnitVectorAngle {
+    /// The angle of zero.
+    pub const ZERO: Self = Self { sin: 0.0, cos: 1.0 };
+
+    /// Constructs the value from the sine and cosine of the angle.
+    ///
+    /// The direction `(cos, sin)` is normalized to unit length.
+    ///
+    /// ## Panics
+    /// Panics if both the sine and cosine are (near) zero.
+    pub fn from_sin_cos(sin: f64, cos: f64) -> Self {
+        let norm = (sin * sin + cos * cos).sqrt();
+        assert!(norm > f64::EPSILON, "sine and cosine must not both be zero");
+        Self {
+            sin: sin / norm,
+            cos: cos / norm,
+        }
+    }
+
+    /// Returns the sine and cosine of the angle.
+    #[inline(always)]
+    pub const fn sin_cos(&self) -> (f64, f64) {
+        (self.sin, self.cos)
+    }
+
+    /// Converts the value into an angle in radians, in range -PI..=PI.
+    pub fn to_angle(&self) -> Angle<f64> {
+        Angle::from_radians(self.sin.atan2(self.cos))
+    }
+}
+
+impl Default for UnitVectorAngle {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl From<Angle<f64>> for UnitVectorAngle {
+    fn from(value: Angle<f64>) -> Self {
+        let (sin, cos) = value.sin_cos();
+        Self::from_sin_cos(sin, cos)
+    }
+}
+
+impl From<UnitVectorAngle> for Angle<f64> {
+    fn from(value: UnitVectorAngle) -> Self {
+        value.to_angle()
+    }
+}
+
+impl Add for UnitVectorAngle {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from_sin_cos(
+            self.sin * rhs.cos + self.cos * rhs.sin,
+            self.cos * rhs.cos - self.sin * rhs.sin,
+        )
+    }
+}
+
+impl Sub for UnitVectorAngle {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Neg for UnitVectorAngle {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            sin: -self.sin,
+            cos: self.cos,
+        }
+    }
+}
+
 impl<T: Default> Default for Angle<T> {
     fn default() -> Self {
         Self(T::default())
@@ -76,3 +275,118 @@ impl Neg for Angle<f64> {
         Self(-self.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let angle = Angle::from_degrees(45.0);
+        let [m00, m01, m10, m11] = angle.to_matrix();
+
+        let half_sqrt = 0.5_f64.sqrt();
+        assert!((m00 - half_sqrt).abs() < 1e-12);
+        assert!((m01 + half_sqrt).abs() < 1e-12);
+        assert!((m10 - half_sqrt).abs() < 1e-12);
+        assert!((m11 - half_sqrt).abs() < 1e-12);
+
+        let restored = Angle::from_matrix(m00, m01, m10, m11);
+        assert!((restored.into_radians() - angle.into_radians()).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_matrix_rejects_scaling() {
+        Angle::from_matrix(2.0, 0.0, 0.0, 2.0);
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let tolerance = Angle::from_degrees(2.0);
+        assert!(Angle::from_degrees(359.0).approx_eq(&Angle::from_degrees(1.0), tolerance));
+        assert!(Angle::from_degrees(1.0).approx_eq(&Angle::from_degrees(359.0), tolerance));
+        assert!(!Angle::from_degrees(357.0).approx_eq(&Angle::from_degrees(1.0), tolerance));
+
+        let angle = Angle::from_degrees(15.0);
+        let round_trip = Angle::from_degrees(angle.into_radians().to_degrees());
+        assert!(angle.approx_eq(&round_trip, Angle::from_radians(1e-12)));
+        assert!(angle.approx_eq(&Angle::from_degrees(16.5), tolerance));
+        assert!(!angle.approx_eq(&Angle::from_degrees(-15.0), tolerance));
+    }
+
+    #[test]
+    fn test_from_radians_and_degrees() {
+        use std::f64::consts::PI;
+        assert_eq!(Angle::from(PI).degrees(), 180.0);
+        assert_eq!(Angle::from(PI), Angle::from_radians(PI));
+
+        let angle: Angle = 0.5.into();
+        assert_eq!(angle.into_radians(), 0.5);
+        assert!((Angle::from_degrees(15.0).degrees() - 15.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_unit_vector_angle_addition() {
+        use std::f64::consts::FRAC_1_SQRT_2;
+        let error = |(sin, cos): (f64, f64), expected: (f64, f64)| {
+            (sin - expected.0).abs().max((cos - expected.1).abs())
+        };
+
+        // 3 × 45° = 135°
+        let step = UnitVectorAngle::from_sin_cos(FRAC_1_SQRT_2, FRAC_1_SQRT_2);
+        let unit = step + step + step;
+        let step = Angle::from_degrees(45.0).into_radians();
+        let naive = Angle::from_radians(step + step + step);
+        let expected = (FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
+        assert!(error(unit.sin_cos(), expected) <= error(naive.sin_cos(), expected));
+        assert!(unit
+            .to_angle()
+            .approx_eq(&Angle::from_degrees(135.0), Angle::from_radians(1e-12)));
+
+        // 360 × 1° = 0°
+        let step = Angle::from_degrees(1.0);
+        let (unit, naive) = (0..360).fold((UnitVectorAngle::ZERO, 0.0), |(unit, naive), _| {
+            (unit + step.into(), naive + step.into_radians())
+        });
+        let expected = (0.0, 1.0);
+        assert!(error(unit.sin_cos(), expected) < error(naive.sin_cos(), expected));
+
+        assert_eq!(
+            UnitVectorAngle::from_sin_cos(1.0, 0.0) - UnitVectorAngle::from_sin_cos(1.0, 0.0),
+            UnitVectorAngle::ZERO
+        );
+    }
+
+    #[test]
+    fn test_normalize_grid() {
+        assert_eq!(
+            Angle::from_degrees(90.0).normalize(),
+            Angle::from_radians(0.0)
+        );
+        assert_eq!(
+            Angle::from_degrees(90.0).normalize_grid(),
+            Angle::from_degrees(90.0)
+        );
+        assert_eq!(
+            Angle::from_degrees(-90.0).normalize_grid(),
+            Angle::from_degrees(-90.0)
+        );
+
+        let angle = Angle::from_degrees(30.0);
+        assert_eq!(angle.normalize_grid(), angle.normalize());
+        let angle = Angle::from_degrees(120.0);
+        assert_eq!(angle.normalize_grid(), angle.normalize());
+    }
+
+    #[test]
+    fn test_sin_cos_snapped() {
+        assert_eq!(Angle::from_degrees(0.0).sin_cos_snapped(), (0.0, 1.0));
+        assert_eq!(Angle::from_degrees(90.0).sin_cos_snapped(), (1.0, 0.0));
+        assert_eq!(Angle::from_degrees(-90.0).sin_cos_snapped(), (-1.0, 0.0));
+        assert_eq!(Angle::from_degrees(180.0).sin_cos_snapped(), (0.0, -1.0));
+
+        let angle = Angle::from_degrees(30.0);
+        assert_eq!(angle.sin_cos_snapped(), angle.sin_cos());
+    }
+}
@@ -0,0 +1,185 @@
+//! Contains the [`BlueNoiseIterator`] type.
+
+use crate::inner::vector::Vector;
+use crate::GridCoord;
+
+/// The number of candidates generated around an active point before it is retired.
+const CANDIDATES: usize = 30;
+
+/// An iterator for stochastic (FM) screening positions within a rectangle.
+///
+/// The positions follow a blue-noise distribution produced by Poisson-disk sampling
+/// (Bridson's algorithm): no two positions are closer than the minimum distance, and
+/// the rectangle is filled until no further position fits. The positions are generated
+/// lazily and are fully determined by the seed.
+pub struct BlueNoiseIterator {
+    width: f64,
+    height: f64,
+    min_distance: f64,
+    cell_size: f64,
+    columns: usize,
+    rows: usize,
+    /// The index of the point occupying each background grid cell, if any.
+    cells: Vec<Option<usize>>,
+    points: Vec<Vector>,
+    /// The indices of the points around which new points may still be placed.
+    active: Vec<usize>,
+    rng: SplitMix64,
+}
+
+impl BlueNoiseIterator {
+    /// Creates a new iterator for a blue-noise distribution within a rectangle.
+    ///
+    /// ## Arguments
+    /// * `width` - The width of the rectangle. Must be positive.
+    /// * `height` - The height of the rectangle. Must be positive.
+    /// * `min_distance` - The minimum distance between any two positions. Must be positive.
+    /// * `seed` - The seed of the random number generator.
+    pub fn new(width: f64, height: f64, min_distance: f64, seed: u64) -> Self {
+        assert!(width > 0.0);
+        assert!(height > 0.0);
+        assert!(min_distance > 0.0);
+
+        // Cells of this size contain at most one point.
+        let cell_size = min_distance / std::f64::consts::SQRT_2;
+        let columns = (width / cell_size).ceil() as usize;
+        let rows = (height / cell_size).ceil() as usize;
+
+        Self {
+            width,
+            height,
+            min_distance,
+            cell_size,
+            columns,
+            rows,
+            cells: vec![None; columns * rows],
+            points: Vec::new(),
+            active: Vec::new(),
+            rng: SplitMix64::new(seed),
+        }
+    }
+
+    /// Determines the background grid cell of a point within the rectangle.
+    fn cell(&self, point: Vector) -> (usize, usize) {
+        let column = ((point.x / self.cell_size) as usize).min(self.columns - 1);
+        let row = ((point.y / self.cell_size) as usize).min(self.rows - 1);
+        (column, row)
+    }
+
+    /// Determines whether a point lies within the rectangle and keeps
+    /// the minimum distance to all previously placed points.
+    fn is_valid(&self, point: Vector) -> bool {
+        if !(0.0..self.width).contains(&point.x) || !(0.0..self.height).contains(&point.y) {
+            return false;
+        }
+
+        let (column, row) = self.cell(point);
+        let min_distance_sq = self.min_distance * self.min_distance;
+        for y in row.saturating_sub(2)..(row + 3).min(self.rows) {
+            for x in column.saturating_sub(2)..(column + 3).min(self.columns) {
+                if let Some(index) = self.cells[y * self.columns + x] {
+                    if (self.points[index] - point).norm_sq() < min_distance_sq {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Places a point and marks it as active.
+    fn insert(&mut self, point: Vector) -> GridCoord {
+        let (column, row) = self.cell(point);
+        let index = self.points.len();
+        self.cells[row * self.columns + column] = Some(index);
+        self.points.push(point);
+        self.active.push(index);
+        GridCoord::new(point.x, point.y)
+    }
+}
+
+impl Iterator for BlueNoiseIterator {
+    type Item = GridCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.points.is_empty() {
+            let point = Vector::new(
+                self.rng.next_f64() * self.width,
+                self.rng.next_f64() * self.height,
+            );
+            return Some(self.insert(point));
+        }
+
+        while !self.active.is_empty() {
+            let slot = (self.rng.next_f64() * self.active.len() as f64) as usize;
+            let origin = self.points[self.active[slot]];
+
+            for _ in 0..CANDIDATES {
+                // Sample uniformly from the annulus between one and two times the minimum distance.
+                let radius = self.min_distance * (1.0 + 3.0 * self.rng.next_f64()).sqrt();
+                let (sin, cos) = (std::f64::consts::TAU * self.rng.next_f64()).sin_cos();
+                let candidate = origin + Vector::new(cos, sin) * radius;
+                if self.is_valid(candidate) {
+                    return Some(self.insert(candidate));
+                }
+            }
+
+            self.active.swap_remove(slot);
+        }
+
+        None
+    }
+}
+
+/// A small deterministic pseudo-random number generator.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Generates a value in range `0..1`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_distance() {
+        let points: Vec<_> = BlueNoiseIterator::new(100.0, 60.0, 4.0, 42).collect();
+        assert!(points.len() > 100);
+
+        for (i, a) in points.iter().enumerate() {
+            assert!((0.0..100.0).contains(&a.x) && (0.0..60.0).contains(&a.y));
+            for b in &points[i + 1..] {
+                let distance = ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt();
+                assert!(distance >= 4.0, "{a:?} and {b:?} are {distance} apart");
+            }
+        }
+    }
+
+    #[test]
+    fn test_seed_is_reproducible() {
+        let a: Vec<_> = BlueNoiseIterator::new(50.0, 50.0, 3.0, 7).collect();
+        let b: Vec<_> = BlueNoiseIterator::new(50.0, 50.0, 3.0, 7).collect();
+        let c: Vec<_> = BlueNoiseIterator::new(50.0, 50.0, 3.0, 8).collect();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+}
@@ -0,0 +1,98 @@
+//! Contains the [`CorneredGrid`] type.
+
+use crate::{GridCoord, GridPositionIterator};
+
+/// The distance below which a grid position is considered to coincide with a corner.
+const EPSILON: f64 = 1e-9;
+
+/// An iterator for the positions on a rotated grid, preceded by the corners of the rectangle.
+///
+/// See [`GridPositionIterator::with_corners`].
+pub struct CorneredGrid {
+    grid: GridPositionIterator,
+    corners: [GridCoord; 4],
+    next_corner: usize,
+}
+
+impl CorneredGrid {
+    pub(crate) fn new(grid: GridPositionIterator) -> Self {
+        let (left, right) = grid.x_bounds();
+        let (top, bottom) = grid.y_bounds();
+        let corners = [
+            GridCoord::new(left, top),
+            GridCoord::new(right, top),
+            GridCoord::new(right, bottom),
+            GridCoord::new(left, bottom),
+        ];
+        Self {
+            grid,
+            corners,
+            next_corner: 0,
+        }
+    }
+
+    /// Determines whether the position coincides with one of the corners.
+    fn is_corner(&self, coord: &GridCoord) -> bool {
+        self.corners.iter().any(|corner| {
+            (corner.x - coord.x).abs() < EPSILON && (corner.y - coord.y).abs() < EPSILON
+        })
+    }
+}
+
+impl Iterator for CorneredGrid {
+    type Item = GridCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(corner) = self.corners.get(self.next_corner) {
+            self.next_corner += 1;
+            return Some(corner.clone());
+        }
+
+        loop {
+            let coord = self.grid.next()?;
+            if !self.is_corner(&coord) {
+                return Some(coord);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let corners = self.corners.len() - self.next_corner;
+        let (_, upper) = self.grid.size_hint();
+        (corners, upper.map(|upper| upper + corners))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::grid;
+    use crate::GridCoord;
+
+    fn count_corners(points: &[GridCoord], width: f64, height: f64) -> Vec<usize> {
+        [(0.0, 0.0), (width, 0.0), (width, height), (0.0, height)]
+            .iter()
+            .map(|&(x, y)| {
+                points
+                    .iter()
+                    .filter(|p| (p.x - x).abs() < 1e-9 && (p.y - y).abs() < 1e-9)
+                    .count()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_corners_on_lattice() {
+        // The nodes are anchored at the center, such that the lattice hits all corners.
+        let points: Vec<_> = grid(10.0, 0.0).with_corners().collect();
+        assert_eq!(count_corners(&points, 60.0, 40.0), [1, 1, 1, 1]);
+        assert_eq!(points.len(), grid(10.0, 0.0).exact_count());
+    }
+
+    #[test]
+    fn test_corners_off_lattice() {
+        let points: Vec<_> = grid(7.0, 15.0).with_corners().collect();
+        assert_eq!(count_corners(&points, 60.0, 40.0), [1, 1, 1, 1]);
+        assert_eq!(points.len(), grid(7.0, 15.0).exact_count() + 4);
+        assert_eq!(&points[4..], grid(7.0, 15.0).collect::<Vec<_>>());
+    }
+}
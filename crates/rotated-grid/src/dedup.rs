@@ -0,0 +1,143 @@
+//! Contains the [`DedupMerge`] type and the spatial hash it is based on.
+
+use crate::{GridCoord, GridPositionIterator};
+use std::collections::HashMap;
+
+/// An iterator merging the positions of multiple grids, suppressing positions
+/// that coincide with an already emitted position.
+///
+/// The grids are iterated in order. Emitted positions are tracked in a spatial hash
+/// whose cells are the size of the tolerance, such that each position only needs to
+/// be compared against the positions in its neighboring cells.
+pub struct DedupMerge {
+    grids: std::vec::IntoIter<GridPositionIterator>,
+    current: Option<GridPositionIterator>,
+    emitted: SpatialHash,
+}
+
+impl DedupMerge {
+    /// Creates a new iterator merging the specified grids.
+    ///
+    /// ## Arguments
+    /// * `grids` - The grids to merge.
+    /// * `tolerance` - The distance below which two positions are considered identical.
+    ///   Must be positive.
+    pub fn new<I>(grids: I, tolerance: f64) -> Self
+    where
+        I: IntoIterator<Item = GridPositionIterator>,
+    {
+        let mut grids = grids.into_iter().collect::<Vec<_>>().into_iter();
+        Self {
+            current: grids.next(),
+            grids,
+            emitted: SpatialHash::new(tolerance),
+        }
+    }
+}
+
+impl Iterator for DedupMerge {
+    type Item = GridCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let grid = self.current.as_mut()?;
+            let coord = match grid.next() {
+                Some(coord) => coord,
+                None => {
+                    self.current = self.grids.next();
+                    continue;
+                }
+            };
+
+            if self.emitted.contains_near(&coord) {
+                continue;
+            }
+
+            self.emitted.insert(coord.clone());
+            return Some(coord);
+        }
+    }
+}
+
+/// A set of positions supporting queries for positions within a tolerance.
+///
+/// The positions are bucketed into cells the size of the tolerance, such that each
+/// query only needs to compare against the positions in the neighboring cells.
+pub(crate) struct SpatialHash {
+    tolerance: f64,
+    cells: HashMap<(i64, i64), Vec<GridCoord>>,
+}
+
+impl SpatialHash {
+    /// Creates an empty set.
+    ///
+    /// ## Panics
+    /// Panics if the tolerance is not positive.
+    pub fn new(tolerance: f64) -> Self {
+        assert!(tolerance > 0.0);
+        Self {
+            tolerance,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Adds a position to the set.
+    pub fn insert(&mut self, coord: GridCoord) {
+        let cell = self.cell(&coord);
+        self.cells.entry(cell).or_default().push(coord);
+    }
+
+    /// Determines whether the set contains a position closer than the tolerance.
+    pub fn contains_near(&self, coord: &GridCoord) -> bool {
+        let (cx, cy) = self.cell(coord);
+        let tolerance_sq = self.tolerance * self.tolerance;
+        (cx - 1..=cx + 1)
+            .flat_map(|x| (cy - 1..=cy + 1).map(move |y| (x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .any(|other| {
+                let dx = other.x - coord.x;
+                let dy = other.y - coord.y;
+                dx * dx + dy * dy < tolerance_sq
+            })
+    }
+
+    /// Determines the cell of a position.
+    fn cell(&self, coord: &GridCoord) -> (i64, i64) {
+        (
+            (coord.x / self.tolerance).floor() as i64,
+            (coord.y / self.tolerance).floor() as i64,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Angle;
+
+    fn grid(x0: f64) -> GridPositionIterator {
+        GridPositionIterator::new(
+            60.0,
+            40.0,
+            7.0,
+            7.0,
+            x0,
+            0.0,
+            Angle::<f64>::from_degrees(15.0),
+        )
+    }
+
+    #[test]
+    fn test_merge_with_itself() {
+        let merged: Vec<_> = DedupMerge::new([grid(0.0), grid(0.0)], 1e-6).collect();
+        let expected: Vec<_> = grid(0.0).collect();
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn test_merge_with_offset_grid() {
+        let merged = DedupMerge::new([grid(0.0), grid(3.5)], 1e-6).count();
+        assert_eq!(merged, grid(0.0).count() + grid(3.5).count());
+    }
+}
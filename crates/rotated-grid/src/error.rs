@@ -0,0 +1,107 @@
+//! Contains the [`GridError`] and [`PixelError`] types.
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// An error returned by the fallible grid constructors.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// The width or height of the grid is zero, negative, infinite or not a number.
+    NonPositiveDimension,
+    /// The spacing of the grid elements is zero, negative, infinite or not a number.
+    NonPositiveSpacing,
+    /// The orientation of the grid is infinite or not a number.
+    NonFiniteAngle,
+    /// The orientation of the grid is outside the supported range of 0..90°.
+    AngleOutOfRange,
+    /// The specified polygon has fewer than three vertices or is not convex.
+    NonConvexPolygon,
+    /// The grid specification could not be parsed.
+    MalformedSpec,
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::NonPositiveDimension => {
+                write!(f, "The width and height of the grid must be positive")
+            }
+            GridError::NonPositiveSpacing => {
+                write!(f, "The spacing of the grid elements must be positive")
+            }
+            GridError::NonFiniteAngle => write!(f, "The orientation of the grid must be finite"),
+            GridError::AngleOutOfRange => {
+                write!(f, "The orientation of the grid must be in range 0..90°")
+            }
+            GridError::NonConvexPolygon => write!(f, "The polygon must be convex"),
+            GridError::MalformedSpec => write!(f, "The grid specification is malformed"),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+/// An error returned when converting a grid coordinate into a pixel position.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PixelError {
+    /// The coordinate is infinite or not a number.
+    NonFinite,
+    /// The coordinate lies left of or above the image.
+    Negative,
+    /// The coordinate lies right of or below the image.
+    OutOfBounds,
+}
+
+impl Display for PixelError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PixelError::NonFinite => write!(f, "The coordinate must be finite"),
+            PixelError::Negative => write!(f, "The coordinate lies before the image"),
+            PixelError::OutOfBounds => write!(f, "The coordinate lies beyond the image"),
+        }
+    }
+}
+
+impl Error for PixelError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            GridError::NonPositiveDimension.to_string(),
+            "The width and height of the grid must be positive"
+        );
+        assert_eq!(
+            GridError::NonPositiveSpacing.to_string(),
+            "The spacing of the grid elements must be positive"
+        );
+        assert_eq!(
+            GridError::NonFiniteAngle.to_string(),
+            "The orientation of the grid must be finite"
+        );
+        assert_eq!(
+            GridError::AngleOutOfRange.to_string(),
+            "The orientation of the grid must be in range 0..90°"
+        );
+        assert_eq!(
+            GridError::NonConvexPolygon.to_string(),
+            "The polygon must be convex"
+        );
+        assert_eq!(
+            GridError::MalformedSpec.to_string(),
+            "The grid specification is malformed"
+        );
+    }
+
+    #[test]
+    fn test_into_boxed_error() {
+        let error: Box<dyn Error> = GridError::NonPositiveSpacing.into();
+        assert_eq!(
+            error.to_string(),
+            "The spacing of the grid elements must be positive"
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Contains the export of grid positions into buffers.
+
+use crate::inner::vector::Vector;
+use crate::{GridCoord, GridPositionIterator};
+
+impl GridPositionIterator {
+    /// Fills the buffer with the next grid positions, returning the number of positions written.
+    ///
+    /// Fewer positions than fit into the buffer are written only if the iterator is exhausted;
+    /// `0` is returned once no positions remain.
+    pub fn next_batch(&mut self, out: &mut [GridCoord]) -> usize {
+        let mut written = 0;
+        for (slot, coord) in out.iter_mut().zip(self.by_ref()) {
+            *slot = coord;
+            written += 1;
+        }
+        written
+    }
+
+    /// Collects up to `n` of the next grid positions, e.g. for generating a bounded number
+    /// of positions per frame in interactive applications.
+    ///
+    /// The iterator is left positioned after the returned positions, such that the next call
+    /// continues where this one stopped. An empty vector is returned once no positions remain.
+    pub fn take_resumable(&mut self, n: usize) -> Vec<GridCoord> {
+        self.by_ref().take(n).collect()
+    }
+
+    /// Collects the remaining grid positions into a contiguous buffer of interleaved
+    /// X and Y coordinates, i.e. `[x0, y0, x1, y1, ...]`, e.g. for passing to C or GPU code.
+    pub fn into_flat_xy(self) -> Vec<f64> {
+        let mut buffer = Vec::with_capacity(2 * self.exact_count());
+        for coord in self {
+            buffer.push(coord.x);
+            buffer.push(coord.y);
+        }
+        buffer
+    }
+
+    /// Like [`GridPositionIterator::into_flat_xy`], but converts the coordinates to `f32`.
+    pub fn into_flat_xy_f32(self) -> Vec<f32> {
+        let mut buffer = Vec::with_capacity(2 * self.exact_count());
+        for coord in self {
+            buffer.push(coord.x as f32);
+            buffer.push(coord.y as f32);
+        }
+        buffer
+    }
+
+    /// Renders an ordered-dither threshold map for an image of the specified size.
+    ///
+    /// Each pixel's threshold is derived from the distance of its center to the nearest
+    /// grid node, relative to the grid cell: pixels at a node have a threshold of `0`,
+    /// pixels at the corners of a cell have a threshold of `255`. Comparing an image's
+    /// intensity against this map thus grows dots around the grid nodes.
+    ///
+    /// The returned buffer is in row-major order and has `width * height` entries.
+    pub fn rasterize_thresholds(self, width: usize, height: usize) -> Vec<u8> {
+        let max_distance = 0.5_f64.sqrt();
+        let mut thresholds = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let pixel = Vector::new(x as f64 + 0.5, y as f64 + 0.5);
+                let lattice = self.to_lattice(pixel);
+                let phase =
+                    Vector::new(lattice.x - lattice.x.round(), lattice.y - lattice.y.round());
+                let value = (phase.norm() / max_distance).min(1.0) * 255.0;
+                thresholds.push(value.round() as u8);
+            }
+        }
+        thresholds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::grid;
+    use crate::Angle;
+
+    #[test]
+    fn test_next_batch() {
+        let expected: Vec<_> = grid(6.0, 25.0).collect();
+
+        let mut grid = grid(6.0, 25.0);
+        let mut buffer = vec![GridCoord::new(0.0, 0.0); 7];
+        let mut actual = Vec::new();
+        loop {
+            let written = grid.next_batch(&mut buffer);
+            if written == 0 {
+                break;
+            }
+            actual.extend_from_slice(&buffer[..written]);
+        }
+
+        assert_eq!(actual, expected);
+        assert_eq!(grid.next_batch(&mut buffer), 0);
+    }
+
+    #[test]
+    fn test_take_resumable() {
+        let expected: Vec<_> = grid(6.0, 25.0).collect();
+
+        let mut grid = grid(6.0, 25.0);
+        let mut actual = Vec::new();
+        loop {
+            let chunk = grid.take_resumable(10);
+            assert!(chunk.len() <= 10);
+            if chunk.is_empty() {
+                break;
+            }
+            actual.extend(chunk);
+        }
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_into_flat_xy() {
+        let grid = || {
+            GridPositionIterator::new(
+                50.0,
+                30.0,
+                6.0,
+                6.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(20.0),
+            )
+        };
+
+        let coords: Vec<_> = grid().collect();
+        let flat = grid().into_flat_xy();
+        assert_eq!(flat.len(), 2 * coords.len());
+        let deinterleaved: Vec<_> = flat
+            .chunks_exact(2)
+            .map(|xy| GridCoord::new(xy[0], xy[1]))
+            .collect();
+        assert_eq!(deinterleaved, coords);
+
+        let flat = grid().into_flat_xy_f32();
+        assert_eq!(flat.len(), 2 * coords.len());
+        for (xy, coord) in flat.chunks_exact(2).zip(&coords) {
+            assert_eq!(xy, [coord.x as f32, coord.y as f32]);
+        }
+    }
+
+    #[test]
+    fn test_rasterize_thresholds() {
+        let grid = GridPositionIterator::new(
+            32.0,
+            24.0,
+            8.0,
+            8.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(15.0),
+        );
+
+        for coord in GridPositionIterator::new(
+            32.0,
+            24.0,
+            8.0,
+            8.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(15.0),
+        ) {
+            let lattice = grid.to_lattice(coord.into_xy().into());
+            assert_vec_eq!(lattice, Vector::new(lattice.x.round(), lattice.y.round()));
+        }
+
+        let thresholds = grid.rasterize_thresholds(32, 24);
+        assert_eq!(thresholds.len(), 32 * 24);
+        assert!(thresholds.iter().any(|&t| t < 32));
+        assert!(thresholds.iter().any(|&t| t > 224));
+    }
+}
@@ -0,0 +1,78 @@
+//! Contains the [`FilteredGrid`] type.
+
+use crate::{GridCoord, GridPositionIterator};
+
+/// An iterator for the positions on a rotated grid that match a predicate.
+///
+/// See [`GridPositionIterator::filtered`].
+pub struct FilteredGrid<F> {
+    grid: GridPositionIterator,
+    predicate: F,
+}
+
+impl<F> FilteredGrid<F>
+where
+    F: Fn(&GridCoord) -> bool,
+{
+    pub(crate) fn new(grid: GridPositionIterator, predicate: F) -> Self {
+        Self { grid, predicate }
+    }
+
+    /// Provides an upper bound for the number of remaining grid positions.
+    ///
+    /// This is the exact number of positions of the unfiltered grid
+    /// (see [`GridPositionIterator::exact_count`]); the predicate is not evaluated.
+    pub fn max_count(&self) -> usize {
+        self.grid.exact_count()
+    }
+}
+
+impl<F> Iterator for FilteredGrid<F>
+where
+    F: Fn(&GridCoord) -> bool,
+{
+    type Item = GridCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let predicate = &self.predicate;
+        self.grid.find(|coord| predicate(coord))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.grid.size_hint().1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Angle, GridPositionIterator};
+
+    #[test]
+    fn test_even_columns() {
+        let grid = || {
+            GridPositionIterator::new(
+                50.0,
+                40.0,
+                7.0,
+                7.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(0.0),
+            )
+        };
+
+        let full: Vec<_> = grid().collect();
+        let min_x = full.iter().map(|c| c.x).fold(f64::INFINITY, f64::min);
+        let column = move |x: f64| ((x - min_x) / 7.0).round() as usize;
+
+        let filtered = grid().filtered(|c| column(c.x) % 2 == 0);
+        assert_eq!(filtered.max_count(), full.len());
+
+        let even: Vec<_> = filtered.collect();
+        assert!(!even.is_empty());
+        assert!(even.iter().all(|c| column(c.x) % 2 == 0));
+
+        let expected: Vec<_> = full.into_iter().filter(|c| column(c.x) % 2 == 0).collect();
+        assert_eq!(even, expected);
+    }
+}
@@ -0,0 +1,17 @@
+//! Contains grids shared across the tests.
+
+use crate::{Angle, GridPositionIterator};
+
+/// Creates a 60×40 grid with the specified spacing along both axes,
+/// rotated by the specified angle in degrees.
+pub(crate) fn grid(spacing: f64, degrees: f64) -> GridPositionIterator {
+    GridPositionIterator::new(
+        60.0,
+        40.0,
+        spacing,
+        spacing,
+        0.0,
+        0.0,
+        Angle::<f64>::from_degrees(degrees),
+    )
+}
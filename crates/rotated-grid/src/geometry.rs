@@ -0,0 +1,258 @@
+//! Contains the geometry of the rectangle and lattice of a grid.
+
+use crate::inner::vector::Vector;
+use crate::GridPositionIterator;
+
+impl GridPositionIterator {
+    /// Returns the corners of the rectangle rotated into grid space, in draw order
+    /// (top left, top right, bottom right, bottom left), e.g. for stroking its outline.
+    pub fn outline(&self) -> [Vector; 4] {
+        let center = self.inner.center();
+        let (sin, cos) = (-self.inv_sin, self.inv_cos);
+        [
+            self.origin,
+            self.origin + Vector::new(self.width, 0.0),
+            self.origin + Vector::new(self.width, self.height),
+            self.origin + Vector::new(0.0, self.height),
+        ]
+        .map(|corner| corner.rotate_around_with(center, sin, cos))
+    }
+
+    /// Returns the 2×2 matrix in row-major order, i.e. `[m00, m01, m10, m11]`, that rotates
+    /// points from the original space into rotated grid space (around the center of the rectangle).
+    pub fn rotation(&self) -> [f64; 4] {
+        let (sin, cos) = (-self.inv_sin, self.inv_cos);
+        [cos, -sin, sin, cos]
+    }
+
+    /// Returns the 2×2 matrix in row-major order, i.e. `[m00, m01, m10, m11]`, that rotates
+    /// points from rotated grid space back into the original space (around the center of the
+    /// rectangle). This is the inverse of [`GridPositionIterator::rotation`].
+    pub fn unrotation(&self) -> [f64; 4] {
+        let (sin, cos) = (self.inv_sin, self.inv_cos);
+        [cos, -sin, sin, cos]
+    }
+
+    /// Transforms a point from the original space into rotated grid space, i.e. the
+    /// space in which the grid is axis-aligned.
+    pub fn to_grid_space(&self, point: Vector) -> Vector {
+        point.rotate_around_with(self.inner.center(), -self.inv_sin, self.inv_cos)
+    }
+
+    /// Returns the basis vectors of the grid lattice in the original space, i.e. the
+    /// rotated `(dx, 0)` and `(0, dy)`. Translating the grid by any integer combination
+    /// of these vectors maps grid nodes onto grid nodes, which allows seamless tiling.
+    pub fn lattice_vectors(&self) -> (Vector, Vector) {
+        (
+            Vector::new(self.dx, 0.0).rotate_with(self.inv_sin, self.inv_cos),
+            Vector::new(0.0, self.dy).rotate_with(self.inv_sin, self.inv_cos),
+        )
+    }
+
+    /// Returns the minimum and maximum X coordinate any emitted position can take,
+    /// e.g. to size a canvas such that no dots are clipped.
+    ///
+    /// Since the grid nodes are generated within the rectangle in rotated grid space,
+    /// the emitted positions lie within the rectangle itself, i.e. its axis-aligned
+    /// bounding box in the original space, up to rounding errors. Widen the bounds by
+    /// the dot radius to avoid clipping dots.
+    pub fn x_bounds(&self) -> (f64, f64) {
+        (self.origin.x, self.origin.x + self.width)
+    }
+
+    /// Returns the minimum and maximum Y coordinate any emitted position can take.
+    /// See [`GridPositionIterator::x_bounds`] for details.
+    pub fn y_bounds(&self) -> (f64, f64) {
+        (self.origin.y, self.origin.y + self.height)
+    }
+
+    /// Returns the width and height of the axis-aligned bounding box that wraps
+    /// the rectangle in rotated grid space, i.e. `width·cos + height·sin`
+    /// and `width·sin + height·cos`.
+    pub fn rotated_extent(&self) -> Vector {
+        *self.inner.extent()
+    }
+
+    /// Returns the area of the rectangle, i.e. `width·height`, which is invariant under rotation.
+    pub fn rotated_rect_area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    /// Returns the area of the axis-aligned bounding box that wraps the rectangle
+    /// in rotated grid space, see [`GridPositionIterator::rotated_extent`].
+    pub fn bounding_box_area(&self) -> f64 {
+        let extent = self.rotated_extent();
+        extent.x * extent.y
+    }
+
+    /// Returns the fraction of the bounding box in rotated grid space covered by the
+    /// rectangle, in range `0..=1`; e.g. `0.5` for a square rotated by 45°.
+    ///
+    /// The rows are scanned across the bounding box, but only the nodes within the
+    /// rectangle are produced. This is why rotated grids produce fewer positions than
+    /// the upper bound given by [`Iterator::size_hint`], which assumes an unrotated grid.
+    pub fn packing_efficiency(&self) -> f64 {
+        self.rotated_rect_area() / self.bounding_box_area()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Angle, GridCoord};
+
+    #[test]
+    fn test_outline() {
+        let grid = GridPositionIterator::new(
+            40.0,
+            20.0,
+            5.0,
+            5.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(90.0),
+        );
+
+        let [tl, tr, br, bl] = grid.outline();
+        assert_vec_eq!(tl, Vector::new(30.0, -10.0));
+        assert_vec_eq!(tr, Vector::new(30.0, 30.0));
+        assert_vec_eq!(br, Vector::new(10.0, 30.0));
+        assert_vec_eq!(bl, Vector::new(10.0, -10.0));
+    }
+
+    #[test]
+    fn test_lattice_vectors() {
+        let grid = || {
+            GridPositionIterator::new(
+                80.0,
+                60.0,
+                7.0,
+                5.0,
+                1.5,
+                -2.0,
+                Angle::<f64>::from_degrees(25.0),
+            )
+        };
+
+        let (u, v) = grid().lattice_vectors();
+        assert!((u.norm() - 7.0).abs() < 1e-9);
+        assert!((v.norm() - 5.0).abs() < 1e-9);
+        assert!(u.dot(&v).abs() < 1e-9);
+
+        let reference = grid();
+        let is_node = |point: Vector| {
+            let lattice = reference.to_lattice(point);
+            (lattice.x - lattice.x.round()).abs() < 1e-9
+                && (lattice.y - lattice.y.round()).abs() < 1e-9
+        };
+
+        for coord in grid() {
+            let node = Vector::new(coord.x, coord.y);
+            assert!(is_node(node));
+            assert!(is_node(node + u));
+            assert!(is_node(node - v));
+            assert!(is_node(node + u * 3.0 + v * 2.0));
+        }
+    }
+
+    #[test]
+    fn test_rotated_extent() {
+        let grid = |angle: f64| {
+            GridPositionIterator::new(
+                100.0,
+                40.0,
+                7.0,
+                7.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(angle),
+            )
+        };
+
+        assert_vec_eq!(grid(0.0).rotated_extent(), Vector::new(100.0, 40.0));
+
+        let (sin, cos) = Angle::<f64>::from_degrees(30.0).sin_cos();
+        let expected = Vector::new(100.0 * cos + 40.0 * sin, 100.0 * sin + 40.0 * cos);
+        assert_vec_eq!(grid(30.0).rotated_extent(), expected);
+    }
+
+    #[test]
+    fn test_bounds_contain_positions() {
+        for angle in [0.0, 15.0, 45.0, 72.0, 90.0] {
+            let grid = GridPositionIterator::new_at(
+                Vector::new(5.0, -3.0),
+                70.0,
+                40.0,
+                6.0,
+                7.0,
+                1.5,
+                2.5,
+                Angle::<f64>::from_degrees(angle),
+            );
+
+            let (min_x, max_x) = grid.x_bounds();
+            let (min_y, max_y) = grid.y_bounds();
+            assert!((min_x - 5.0).abs() < 1e-9 && (max_x - 75.0).abs() < 1e-9);
+            assert!((min_y + 3.0).abs() < 1e-9 && (max_y - 37.0).abs() < 1e-9);
+
+            for coord in grid {
+                assert!(min_x - 1e-9 <= coord.x && coord.x <= max_x + 1e-9);
+                assert!(min_y - 1e-9 <= coord.y && coord.y <= max_y + 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_packing_efficiency() {
+        let grid = |angle: f64| {
+            GridPositionIterator::new(
+                40.0,
+                40.0,
+                4.0,
+                4.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(angle),
+            )
+        };
+
+        assert_eq!(grid(0.0).rotated_rect_area(), 1600.0);
+        assert_eq!(grid(45.0).rotated_rect_area(), 1600.0);
+        assert!((grid(0.0).packing_efficiency() - 1.0).abs() < 1e-9);
+        assert!((grid(45.0).bounding_box_area() - 3200.0).abs() < 1e-9);
+        assert!((grid(45.0).packing_efficiency() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_grid_space_round_trip() {
+        let grid = GridPositionIterator::new(
+            80.0,
+            60.0,
+            5.0,
+            5.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(35.0),
+        );
+
+        for point in [
+            Vector::new(0.0, 0.0),
+            Vector::new(12.5, -3.0),
+            Vector::new(80.0, 60.0),
+        ] {
+            let unrotated = grid.unrotate(grid.to_grid_space(point));
+            assert_coord_eq!(unrotated, GridCoord::new(point.x, point.y));
+        }
+
+        let [a, b, c, d] = grid.rotation();
+        let [e, f, g, h] = grid.unrotation();
+        let product = [a * e + b * g, a * f + b * h, c * e + d * g, c * f + d * h];
+        for (actual, expected) in product.iter().zip([1.0, 0.0, 0.0, 1.0]) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+        assert_eq!(
+            grid.rotation(),
+            Angle::<f64>::from_degrees(35.0).to_matrix()
+        );
+    }
+}
@@ -1,5 +1,37 @@
+use crate::PixelError;
 use std::cmp::Ordering;
 
+/// The rounding applied when converting a [`GridCoord`] into a pixel position.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Rounding {
+    /// Rounds towards negative infinity, i.e. selects the pixel containing the coordinate
+    /// if pixels span from their integer position to the next.
+    Floor,
+    /// Rounds to the nearest integer, i.e. selects the pixel containing the coordinate
+    /// if pixels are centered on their integer position.
+    Nearest,
+    /// Rounds towards positive infinity.
+    Ceil,
+}
+
+impl Default for Rounding {
+    fn default() -> Self {
+        Rounding::Floor
+    }
+}
+
+impl Rounding {
+    /// Applies the rounding to a value.
+    #[inline(always)]
+    fn apply(self, value: f64) -> f64 {
+        match self {
+            Rounding::Floor => value.floor(),
+            Rounding::Nearest => value.round(),
+            Rounding::Ceil => value.ceil(),
+        }
+    }
+}
+
 /// A coordinate on the grid.
 #[derive(Debug, Clone, PartialEq)]
 pub struct GridCoord {
@@ -21,6 +53,54 @@ impl GridCoord {
     pub const fn into_xy(self) -> (f64, f64) {
         (self.x, self.y)
     }
+
+    /// Converts the coordinate into the position of a pixel in an image of the specified size.
+    ///
+    /// ## Errors
+    /// Returns a [`PixelError`] describing why the (rounded) coordinate does not
+    /// correspond to a pixel of the image.
+    pub fn try_into_pixel(
+        &self,
+        width: u32,
+        height: u32,
+        rounding: Rounding,
+    ) -> Result<(u32, u32), PixelError> {
+        let x = rounding.apply(self.x);
+        let y = rounding.apply(self.y);
+        if !x.is_finite() || !y.is_finite() {
+            return Err(PixelError::NonFinite);
+        }
+
+        if x < 0.0 || y < 0.0 {
+            return Err(PixelError::Negative);
+        }
+
+        if x >= width as f64 || y >= height as f64 {
+            return Err(PixelError::OutOfBounds);
+        }
+
+        Ok((x as u32, y as u32))
+    }
+
+    /// Determines whether the coordinates differ by no more than the tolerance.
+    #[inline(always)]
+    pub fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        (self.x - other.x).abs() <= tolerance && (self.y - other.y).abs() <= tolerance
+    }
+
+    /// Calculates the Manhattan (taxicab) distance to another coordinate,
+    /// i.e. the sum of the absolute differences along both axes.
+    #[inline(always)]
+    pub fn manhattan(&self, other: &Self) -> f64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Calculates the Chebyshev (chessboard) distance to another coordinate,
+    /// i.e. the largest absolute difference along either axis.
+    #[inline(always)]
+    pub fn chebyshev(&self, other: &Self) -> f64 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
 }
 
 impl PartialOrd for GridCoord {
@@ -32,6 +112,9 @@ impl PartialOrd for GridCoord {
     }
 }
 
+/// Converts a tuple of X and Y coordinates, in that order, into a [`GridCoord`].
+///
+/// Iterators yielding tuples can be adapted using [`AsCoords::as_coords`].
 impl From<(f64, f64)> for GridCoord {
     fn from(value: (f64, f64)) -> Self {
         Self::new(value.0, value.1)
@@ -43,3 +126,93 @@ impl From<GridCoord> for (f64, f64) {
         value.into_xy()
     }
 }
+
+/// Adapts iterators yielding tuples of X and Y coordinates, e.g. code written against
+/// tuple-based grid iterators, into iterators yielding [`GridCoord`]s.
+pub trait AsCoords: Iterator<Item = (f64, f64)> + Sized {
+    /// Converts each tuple of X and Y coordinates, in that order, into a [`GridCoord`].
+    // The name mirrors the tuple-based API it migrates from, even though it consumes the iterator.
+    #[allow(clippy::wrong_self_convention)]
+    fn as_coords(self) -> TupleCoords<Self> {
+        TupleCoords { iter: self }
+    }
+}
+
+impl<I> AsCoords for I where I: Iterator<Item = (f64, f64)> {}
+
+/// An iterator converting tuples of X and Y coordinates into [`GridCoord`]s.
+///
+/// See [`AsCoords::as_coords`].
+pub struct TupleCoords<I> {
+    iter: I,
+}
+
+impl<I> Iterator for TupleCoords<I>
+where
+    I: Iterator<Item = (f64, f64)>,
+{
+    type Item = GridCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(GridCoord::from)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::grid;
+
+    #[test]
+    fn test_try_into_pixel() {
+        let coord = GridCoord::new(9.5, 0.4);
+        assert_eq!(coord.try_into_pixel(10, 10, Rounding::Floor), Ok((9, 0)));
+        assert_eq!(
+            coord.try_into_pixel(10, 10, Rounding::Nearest),
+            Err(PixelError::OutOfBounds)
+        );
+        assert_eq!(
+            coord.try_into_pixel(10, 10, Rounding::Ceil),
+            Err(PixelError::OutOfBounds)
+        );
+
+        let coord = GridCoord::new(-0.4, 3.0);
+        assert_eq!(
+            coord.try_into_pixel(10, 10, Rounding::Floor),
+            Err(PixelError::Negative)
+        );
+        assert_eq!(coord.try_into_pixel(10, 10, Rounding::Nearest), Ok((0, 3)));
+        assert_eq!(coord.try_into_pixel(10, 10, Rounding::Ceil), Ok((0, 3)));
+
+        assert_eq!(
+            GridCoord::new(f64::NAN, 0.0).try_into_pixel(10, 10, Rounding::Floor),
+            Err(PixelError::NonFinite)
+        );
+    }
+
+    #[test]
+    fn test_as_coords() {
+        let tuples: Vec<(f64, f64)> = grid(7.0, 0.0).map(GridCoord::into_xy).collect();
+        let coords: Vec<GridCoord> = tuples.into_iter().as_coords().collect();
+        assert_eq!(coords, grid(7.0, 0.0).collect::<Vec<_>>());
+
+        let empty: Vec<(f64, f64)> = Vec::new();
+        assert_eq!(empty.into_iter().as_coords().next(), None);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = GridCoord::new(1.0, 2.0);
+        let b = GridCoord::new(4.0, -2.0);
+        assert_eq!(a.manhattan(&b), 7.0);
+        assert_eq!(a.chebyshev(&b), 4.0);
+        assert_eq!(b.manhattan(&a), 7.0);
+        assert_eq!(b.chebyshev(&a), 4.0);
+        assert_eq!(a.manhattan(&a), 0.0);
+        assert_eq!(a.chebyshev(&a), 0.0);
+    }
+}
@@ -0,0 +1,102 @@
+//! Contains the computation of the convex hull of grid positions.
+
+use crate::inner::vector::{signed_triangle_area, Vector};
+use crate::{GridCoord, GridPositionIterator};
+
+impl GridPositionIterator {
+    /// Consumes the iterator and returns the convex hull of the remaining positions,
+    /// e.g. for approximating the region covered by the grid.
+    ///
+    /// The hull vertices are returned in counterclockwise order with respect to the
+    /// coordinate axes, starting at the position with the smallest X coordinate.
+    /// Positions lying on a hull edge are not included. Since this performs a monotone
+    /// chain scan, all positions are collected and sorted, which takes O(n log n) time.
+    pub fn convex_hull(self) -> Vec<GridCoord> {
+        let mut points: Vec<_> = self.map(|coord| Vector::new(coord.x, coord.y)).collect();
+        points.sort_by(|a, b| {
+            (a.x, a.y)
+                .partial_cmp(&(b.x, b.y))
+                .expect("coordinates are finite")
+        });
+        points.dedup();
+        if points.len() < 3 {
+            return points
+                .into_iter()
+                .map(|p| GridCoord::new(p.x, p.y))
+                .collect();
+        }
+
+        // Nodes along a lattice row are collinear up to rounding noise, which must not
+        // turn them into hull vertices.
+        let (first, last) = (points[0], points[points.len() - 1]);
+        let tolerance = 1e-12 * (last - first).norm().powi(2);
+
+        let mut hull: Vec<Vector> = Vec::with_capacity(points.len() + 1);
+        let push = |hull: &mut Vec<Vector>, point: Vector, floor: usize| {
+            while hull.len() >= floor
+                && signed_triangle_area(hull[hull.len() - 2], hull[hull.len() - 1], point)
+                    <= tolerance
+            {
+                hull.pop();
+            }
+            hull.push(point);
+        };
+
+        // Lower chain from left to right, then upper chain from right to left.
+        for &point in &points {
+            push(&mut hull, point, 2);
+        }
+        let floor = hull.len() + 1;
+        for &point in points.iter().rev().skip(1) {
+            push(&mut hull, point, floor);
+        }
+
+        // The last vertex is the first one again.
+        hull.pop();
+        hull.into_iter().map(|p| GridCoord::new(p.x, p.y)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::grid;
+
+    #[test]
+    fn test_convex_hull() {
+        let points: Vec<_> = grid(7.0, 0.0).collect();
+        let min_x = points.iter().map(|p| p.x).fold(f64::INFINITY, f64::min);
+        let max_x = points.iter().map(|p| p.x).fold(f64::NEG_INFINITY, f64::max);
+        let min_y = points.iter().map(|p| p.y).fold(f64::INFINITY, f64::min);
+        let max_y = points.iter().map(|p| p.y).fold(f64::NEG_INFINITY, f64::max);
+
+        let hull = grid(7.0, 0.0).convex_hull();
+        let expected = [
+            (min_x, min_y),
+            (max_x, min_y),
+            (max_x, max_y),
+            (min_x, max_y),
+        ];
+        assert_eq!(hull.len(), expected.len());
+        for (vertex, (x, y)) in hull.iter().zip(expected) {
+            assert!((vertex.x - x).abs() < 1e-9 && (vertex.y - y).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_convex_hull_contains_all_positions() {
+        let hull: Vec<_> = grid(7.0, 15.0)
+            .convex_hull()
+            .into_iter()
+            .map(|p| Vector::new(p.x, p.y))
+            .collect();
+        assert!(hull.len() >= 3);
+        for coord in grid(7.0, 15.0) {
+            let point = Vector::new(coord.x, coord.y);
+            for (index, &a) in hull.iter().enumerate() {
+                let b = hull[(index + 1) % hull.len()];
+                assert!(signed_triangle_area(a, b, point) >= -1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Contains the conversion of grids into `image` crate masks.
+
+use crate::GridPositionIterator;
+use image::{GrayImage, Luma};
+
+impl GridPositionIterator {
+    /// Rasterizes filled dots at the remaining grid positions into a grayscale mask.
+    ///
+    /// Pixels whose centers lie within `dot_radius` of a grid position are set to `255`,
+    /// all other pixels are `0`. Dots extending beyond the image are cropped.
+    ///
+    /// ## Arguments
+    /// * `width` - The width of the mask in pixels.
+    /// * `height` - The height of the mask in pixels.
+    /// * `dot_radius` - The radius of the dots in pixels.
+    pub fn to_luma_mask(self, width: u32, height: u32, dot_radius: u32) -> GrayImage {
+        let mut mask = GrayImage::new(width, height);
+        let radius = dot_radius as f64;
+        let radius_sq = radius * radius;
+
+        for coord in self {
+            let min_x = (coord.x - radius).floor().max(0.0) as u32;
+            let min_y = (coord.y - radius).floor().max(0.0) as u32;
+            let max_x = ((coord.x + radius).ceil().max(0.0) as u32).min(width);
+            let max_y = ((coord.y + radius).ceil().max(0.0) as u32).min(height);
+
+            for y in min_y..max_y {
+                for x in min_x..max_x {
+                    let dx = x as f64 + 0.5 - coord.x;
+                    let dy = y as f64 + 0.5 - coord.y;
+                    if dx * dx + dy * dy <= radius_sq {
+                        mask.put_pixel(x, y, Luma([255]));
+                    }
+                }
+            }
+        }
+
+        mask
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inner::vector::Vector;
+    use crate::{Angle, GridPositionIterator};
+
+    #[test]
+    fn test_dot_area() {
+        // Keep all dots within the mask such that none are cropped.
+        let grid = || {
+            GridPositionIterator::new_at(
+                Vector::new(10.0, 10.0),
+                80.0,
+                80.0,
+                12.0,
+                12.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(30.0),
+            )
+        };
+
+        let count = grid().count();
+        let mask = grid().to_luma_mask(100, 100, 4);
+        let nonzero = mask.pixels().filter(|pixel| pixel.0[0] != 0).count();
+
+        let expected = count as f64 * std::f64::consts::PI * 16.0;
+        assert!(
+            (nonzero as f64 - expected).abs() < 0.1 * expected,
+            "{nonzero} vs. {expected}"
+        );
+    }
+}
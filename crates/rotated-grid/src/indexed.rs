@@ -0,0 +1,69 @@
+//! Contains the [`IndexedGrid`] and [`IndexedCoord`] types.
+
+use crate::{GridCoord, GridPositionIterator};
+
+/// A grid coordinate along with its sequential index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedCoord {
+    /// The number of coordinates emitted before this one.
+    pub index: usize,
+    /// The coordinate on the grid.
+    pub coord: GridCoord,
+}
+
+/// An iterator for positions on a rotated grid along with their sequential index.
+///
+/// See [`GridPositionIterator::with_index`].
+pub struct IndexedGrid {
+    grid: GridPositionIterator,
+    index: usize,
+}
+
+impl IndexedGrid {
+    pub(crate) fn new(grid: GridPositionIterator) -> Self {
+        Self { grid, index: 0 }
+    }
+}
+
+impl Iterator for IndexedGrid {
+    type Item = IndexedCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let coord = self.grid.next()?;
+        let index = self.index;
+        self.index += 1;
+        Some(IndexedCoord { index, coord })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.grid.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inner::vector::Vector;
+    use crate::{Angle, GridPositionIterator};
+
+    #[test]
+    fn test_indices_are_contiguous() {
+        let grid = GridPositionIterator::new_cropped(
+            (100.0, 80.0),
+            (Vector::new(20.0, 10.0), Vector::new(60.0, 45.0)),
+            7.0,
+            7.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(15.0),
+        );
+        let expected = grid.exact_count();
+
+        let mut count = 0;
+        for (expected_index, indexed) in grid.with_index().enumerate() {
+            assert_eq!(indexed.index, expected_index);
+            count += 1;
+        }
+
+        assert_eq!(count, expected);
+    }
+}
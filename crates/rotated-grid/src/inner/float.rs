@@ -0,0 +1,73 @@
+//! Contains the [`Float`] trait.
+
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+/// A floating-point type usable as the scalar of the [`Vector`](crate::inner::vector::Vector),
+/// [`Line`](crate::inner::line::Line) and [`LineSegment`](crate::inner::line_segment::LineSegment) types.
+pub trait Float:
+    Copy
+    + Debug
+    + Default
+    + PartialOrd
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + SubAssign
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// The value `0`.
+    const ZERO: Self;
+
+    /// The value `1`.
+    const ONE: Self;
+
+    /// The machine epsilon, i.e. the difference between `1` and the next larger value.
+    const EPSILON: Self;
+
+    /// The tolerance below which the determinant of two unit directions
+    /// is considered zero, i.e. the directions are considered parallel.
+    const PARALLEL_EPSILON: Self;
+
+    /// Calculates the square root of the value.
+    fn sqrt(self) -> Self;
+
+    /// Calculates the absolute value.
+    fn abs(self) -> Self;
+}
+
+impl Float for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const EPSILON: Self = f64::EPSILON;
+    const PARALLEL_EPSILON: Self = 1e-6;
+
+    #[inline(always)]
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    #[inline(always)]
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+}
+
+impl Float for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const EPSILON: Self = f32::EPSILON;
+    const PARALLEL_EPSILON: Self = 1e-4;
+
+    #[inline(always)]
+    fn sqrt(self) -> Self {
+        f32::sqrt(self)
+    }
+
+    #[inline(always)]
+    fn abs(self) -> Self {
+        f32::abs(self)
+    }
+}
@@ -1,55 +1,83 @@
 //! Contains the [`Line`] type.
 
+use crate::inner::float::Float;
 use crate::inner::vector::Vector;
 use std::ops::{Mul, Neg};
 
 /// A line determined by a ray starting at a point of origin.
 #[derive(Debug, Clone)]
-pub struct Line {
+pub struct Line<T = f64> {
     /// The origin point of the line.
-    origin: Vector,
+    origin: Vector<T>,
     /// The direction vector of the line.
-    direction: Vector,
+    direction: Vector<T>,
 }
 
-impl Line {
+impl<T: Float> Line<T> {
     /// Constructs a line from an origin point and a direction.
-    pub fn new(origin: Vector, direction: Vector) -> Self {
+    ///
+    /// If the direction is a zero vector, the line's direction consists of `NaN` values;
+    /// use [`Line::try_new`] if the direction may be zero.
+    pub fn new(origin: Vector<T>, direction: Vector<T>) -> Self {
         Self {
             origin,
             direction: direction.normalized(),
         }
     }
 
+    /// Constructs a line from an origin point and a direction,
+    /// or returns `None` if the direction is a (near) zero vector.
+    pub fn try_new(origin: Vector<T>, direction: Vector<T>) -> Option<Self> {
+        Some(Self {
+            origin,
+            direction: direction.try_normalized()?,
+        })
+    }
+
     /// Constructs a line through two points.
+    ///
+    /// If the points coincide, the line's direction consists of `NaN` values;
+    /// use [`Line::try_from_points`] if the points may coincide.
     #[inline(always)]
-    pub fn from_points(a: Vector, b: &Vector) -> Self {
+    pub fn from_points(a: Vector<T>, b: &Vector<T>) -> Self {
         Self::new(a, *b - a)
     }
 
+    /// Constructs a line through two points, or returns `None` if the points (nearly) coincide.
+    #[inline(always)]
+    pub fn try_from_points(a: Vector<T>, b: &Vector<T>) -> Option<Self> {
+        Self::try_new(a, *b - a)
+    }
+}
+
+impl<T> Line<T> {
     #[inline(always)]
-    pub const fn origin(&self) -> &Vector {
+    pub const fn origin(&self) -> &Vector<T> {
         &self.origin
     }
 
     #[inline(always)]
-    pub const fn direction(&self) -> &Vector {
+    pub const fn direction(&self) -> &Vector<T> {
         &self.direction
     }
+}
 
+impl<T: Float> Line<T> {
     /// Projects a vector at a given distance alongside a direction
     /// from the current origin.
     #[inline(always)]
-    pub fn project_out(&self, t: f64) -> Vector {
-        Vector {
-            x: self.origin.x + self.direction.x * t,
-            y: self.origin.y + self.direction.y * t,
-        }
+    pub fn project_out(&self, t: T) -> Vector<T> {
+        self.origin.project_out(&self.direction, t)
     }
 
-    pub fn calculate_intersection_t(&self, other: &Self, max_u: f64) -> Option<f64> {
+    /// Determines the length along this line to its intersection with the segment
+    /// of the specified length starting at the origin of `other`.
+    ///
+    /// Returns `None` if the lines are parallel, or if the intersection lies behind the origin
+    /// of this line or outside the segment, up to a rounding tolerance relative to its length.
+    pub fn calculate_intersection_t(&self, other: &Self, length: T) -> Option<T> {
         let det = self.direction.cross(other.direction());
-        if det.abs() < 1e-6 {
+        if det.abs() < T::PARALLEL_EPSILON {
             // Lines are either parallel or coincident
             return None;
         }
@@ -62,10 +90,12 @@ impl Line {
         // Project the intersection point out.
         let projected = delta.project_out(&self.direction, t);
 
-        // Squared length along other to the point of intersection.
+        // Length along other to the point of intersection.
         let u = projected.dot(&other.direction);
 
-        if t >= 0.0 && u >= 0.0 && u <= max_u * max_u {
+        // Intersections at either end of the segment may come out slightly outside due to rounding.
+        let tolerance = T::PARALLEL_EPSILON * length;
+        if t >= T::ZERO && u >= -tolerance && u <= length + tolerance {
             Some(t)
         } else {
             None
@@ -73,8 +103,8 @@ impl Line {
     }
 }
 
-impl Neg for Line {
-    type Output = Line;
+impl<T: Float> Neg for Line<T> {
+    type Output = Line<T>;
 
     fn neg(self) -> Self::Output {
         Self {
@@ -84,10 +114,80 @@ impl Neg for Line {
     }
 }
 
-impl Mul<f64> for Line {
-    type Output = Vector;
+impl<T: Float> Mul<T> for Line<T> {
+    type Output = Vector<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        self.origin + self.direction * rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_new() {
+        assert!(Line::try_new(Vector::new(1.0, 2.0), Vector::new(0.0, 0.0)).is_none());
+        assert!(Line::try_new(Vector::new(1.0, 2.0), Vector::new(0.0, 3.0)).is_some());
+    }
+
+    #[test]
+    fn test_try_from_points() {
+        let point = Vector::new(1.0, 2.0);
+        assert!(Line::try_from_points(point, &point).is_none());
+        assert!(Line::try_from_points(point, &Vector::new(2.0, 2.0)).is_some());
+    }
+
+    #[test]
+    fn test_intersection_f64() {
+        let horizontal = Line::from_points(Vector::new(0.0, 1.0), &Vector::new(4.0, 1.0));
+        let vertical = Line::from_points(Vector::new(2.0, 0.0), &Vector::new(2.0, 4.0));
+
+        let t = horizontal
+            .calculate_intersection_t(&vertical, 4.0)
+            .expect("lines intersect");
+        assert!((t - 2.0).abs() < 1e-12);
+        assert_vec_eq!(horizontal.project_out(t), Vector::new(2.0, 1.0));
+    }
+
+    #[test]
+    fn test_intersection_at_origin_of_other() {
+        // The ray touches the edge exactly at the edge's origin, where rounding
+        // makes the distance along the edge come out slightly negative.
+        let r = 2.0_f64.sqrt();
+        let edge = Line::from_points(Vector::new(r, 0.0), &Vector::new(0.0, r));
+        let ray = Line::from_points(Vector::new(-r, 0.0), &Vector::new(r, 0.0));
+        let t = ray
+            .calculate_intersection_t(&edge, 2.0)
+            .expect("ray touches the edge");
+        assert!((t - 2.0 * r).abs() < 1e-9);
+
+        // Rays clearly passing before the origin of the edge miss it.
+        let ray = Line::from_points(Vector::new(-r, -0.1), &Vector::new(r, -0.1));
+        assert!(ray.calculate_intersection_t(&edge, 2.0).is_none());
+    }
+
+    #[test]
+    fn test_intersection_f32() {
+        let horizontal: Line<f32> =
+            Line::from_points(Vector::new(0.0, 1.0), &Vector::new(4.0, 1.0));
+        let diagonal: Line<f32> = Line::from_points(Vector::new(0.0, 0.0), &Vector::new(3.0, 3.0));
+
+        let t = diagonal
+            .calculate_intersection_t(&horizontal, 4.0)
+            .expect("lines intersect");
+        assert!((t - 2.0_f32.sqrt()).abs() < 1e-5);
+
+        let point = diagonal.project_out(t);
+        assert!((point.x - 1.0).abs() < 1e-5);
+        assert!((point.y - 1.0).abs() < 1e-5);
+    }
 
-    fn mul(self, rhs: f64) -> Self::Output {
-        self.origin + rhs * self.direction
+    #[test]
+    fn test_parallel_f32() {
+        let a: Line<f32> = Line::from_points(Vector::new(0.0, 0.0), &Vector::new(1000.0, 1.0));
+        let b: Line<f32> = Line::from_points(Vector::new(0.0, 5.0), &Vector::new(1000.0, 6.00001));
+        assert!(a.calculate_intersection_t(&b, 1000.0).is_none());
     }
 }
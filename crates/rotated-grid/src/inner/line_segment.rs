@@ -1,19 +1,20 @@
 //! Contains the [`LineSegment`] type.
 
+use crate::inner::float::Float;
 use crate::inner::line::Line;
 use crate::inner::vector::Vector;
 
 /// A line segment determined by a ray starting at a point of origin with a specified length and direction.
-pub struct LineSegment {
+pub struct LineSegment<T = f64> {
     /// The origin point of the line segment.
-    pub(crate) start: Vector,
+    pub(crate) start: Vector<T>,
     /// The length and direction vector of the line segment.
-    pub(crate) direction: Vector,
+    pub(crate) direction: Vector<T>,
 }
 
-impl LineSegment {
+impl<T: Float> LineSegment<T> {
     /// Constructs a line from an origin point and a direction.
-    pub fn new(origin: Vector, length: Vector) -> Self {
+    pub fn new(origin: Vector<T>, length: Vector<T>) -> Self {
         Self {
             start: origin,
             direction: length,
@@ -22,27 +23,61 @@ impl LineSegment {
 
     /// Constructs a line through two points.
     #[inline(always)]
-    pub fn from_points(a: Vector, b: &Vector) -> Self {
+    pub fn from_points(a: Vector<T>, b: &Vector<T>) -> Self {
         Self::new(a, *b - a)
     }
 
     /// Gets a normalized length version of the line.
     #[inline(always)]
-    pub fn normalized(&self) -> Line {
+    pub fn normalized(&self) -> Line<T> {
         Line::new(self.start, self.direction)
     }
 
-    pub fn direction_normalized(&self) -> Vector {
-        (self.direction - self.start).normalized()
+    /// Gets the unit direction of the line segment.
+    pub fn direction_normalized(&self) -> Vector<T> {
+        self.direction.normalized()
     }
 
+    /// Gets the length of the line segment.
     #[inline(always)]
-    pub const fn start(&self) -> &Vector {
+    pub fn length(&self) -> T {
+        self.direction.norm()
+    }
+
+    /// Gets the point halfway between the start and the end of the line segment.
+    #[inline(always)]
+    pub fn midpoint(&self) -> Vector<T> {
+        self.start + self.direction / (T::ONE + T::ONE)
+    }
+}
+
+impl<T> LineSegment<T> {
+    #[inline(always)]
+    pub const fn start(&self) -> &Vector<T> {
         &self.start
     }
 
     #[inline(always)]
-    pub const fn direction(&self) -> &Vector {
+    pub const fn direction(&self) -> &Vector<T> {
         &self.direction
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_length_and_midpoint() {
+        let segment = LineSegment::from_points(Vector::new(0.0, 0.0), &Vector::new(3.0, 4.0));
+        assert_eq!(segment.length(), 5.0);
+        assert_eq!(segment.midpoint(), Vector::new(1.5, 2.0));
+        assert_vec_eq!(segment.direction_normalized(), Vector::new(0.6, 0.8));
+    }
+
+    #[test]
+    fn test_direction_normalized_away_from_origin() {
+        let segment = LineSegment::from_points(Vector::new(2.0, 2.0), &Vector::new(5.0, 6.0));
+        assert_vec_eq!(segment.direction_normalized(), Vector::new(0.6, 0.8));
+    }
+}
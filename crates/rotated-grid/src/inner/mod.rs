@@ -1,5 +1,6 @@
 //! Internal types exposed mainly for demo use.
 
+pub mod float;
 pub mod line;
 pub mod line_segment;
 pub(crate) mod optimal_iterator;
@@ -2,11 +2,107 @@ use crate::inner::line::Line;
 use crate::inner::vector::Vector;
 use crate::Angle;
 
+/// Determines whether grid nodes lying exactly on the end of a row (or the last row)
+/// are produced.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Boundary {
+    /// Nodes on the end are produced, i.e. rows cover the closed interval `[start, end]`.
+    Inclusive,
+    /// Nodes on the end are skipped, i.e. rows cover the half-open interval `[start, end)`.
+    /// This avoids producing nodes twice at the seam of adjacent tiles.
+    Exclusive,
+}
+
+impl Default for Boundary {
+    fn default() -> Self {
+        Boundary::Inclusive
+    }
+}
+
+impl Boundary {
+    /// Determines whether the value lies beyond the specified end.
+    #[inline(always)]
+    fn exceeds(self, value: f64, end: f64) -> bool {
+        match self {
+            Boundary::Inclusive => value > end,
+            Boundary::Exclusive => value >= end,
+        }
+    }
+}
+
+/// The intersection tests of a single row with the edges of the rotated rectangle,
+/// for diagnosing missing positions. See [`GridPositionIterator::debug_row`](crate::GridPositionIterator::debug_row).
+///
+/// The intersection parameters `t` are distances along the row, measured from the left
+/// edge of the axis-aligned bounding box of the rotated rectangle. An edge without a
+/// valid intersection, e.g. because it is parallel to the row or the intersection lies
+/// beyond the edge, is reported as `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowDebug {
+    /// The Y coordinate of the row in rotated grid space.
+    pub y: f64,
+    /// The intersection with the top edge of the rectangle.
+    pub top: Option<f64>,
+    /// The intersection with the left edge of the rectangle.
+    pub left: Option<f64>,
+    /// The intersection with the bottom edge of the rectangle.
+    pub bottom: Option<f64>,
+    /// The intersection with the right edge of the rectangle.
+    pub right: Option<f64>,
+    /// The smallest valid intersection, i.e. the start of the row.
+    pub min_t: Option<f64>,
+    /// The largest valid intersection, i.e. the end of the row.
+    pub max_t: Option<f64>,
+}
+
+/// The corner of the rotated rectangle at which the iteration starts.
+///
+/// Rows are visited from the starting corner's edge towards the opposite edge, and
+/// the coordinates of each row are visited from the starting corner's side.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Corner {
+    /// Rows are visited top to bottom, each from left to right.
+    TopLeft,
+    /// Rows are visited top to bottom, each from right to left.
+    TopRight,
+    /// Rows are visited bottom to top, each from left to right.
+    BottomLeft,
+    /// Rows are visited bottom to top, each from right to left.
+    BottomRight,
+}
+
+impl Default for Corner {
+    fn default() -> Self {
+        Corner::TopLeft
+    }
+}
+
+impl Corner {
+    /// Determines whether rows are visited bottom to top.
+    #[inline(always)]
+    fn is_bottom(self) -> bool {
+        matches!(self, Corner::BottomLeft | Corner::BottomRight)
+    }
+
+    /// Determines whether the coordinates of each row are visited right to left.
+    #[inline(always)]
+    fn is_right(self) -> bool {
+        matches!(self, Corner::TopRight | Corner::BottomRight)
+    }
+}
+
 /// An iterator for grid coordinates in rotated rectangle space.
 /// Only coordinates that are guaranteed to lie within the original
 /// axis-aligned rectangle are produced.
 pub struct OptimalIterator {
+    /// The Y coordinate of the next row to visit.
     y: f64,
+    /// The Y coordinate of the row currently being iterated.
+    row_y: f64,
+    /// The Y coordinate of the topmost row.
+    start_y: f64,
+    /// The Y coordinate of the first row to visit.
+    first_y: f64,
     min_x: f64,
     max_y: f64,
     center: Vector,
@@ -21,11 +117,27 @@ pub struct OptimalIterator {
     rect_bottom: Line,
     /// The line segment describing the right edge of the rotated rectangle.
     rect_right: Line,
+    /// The lengths of the horizontal and vertical edges of the rectangle.
+    edge_lengths: Vector,
     x_iter: Option<OptimalXIterator>,
+    /// Whether the rotated rectangle is axis-aligned, i.e. rotated by 0° or 90°,
+    /// in which case every row spans the entire bounding box.
+    axis_aligned: bool,
+    boundary: Boundary,
+    corner: Corner,
+    /// Whether the direction of the rows alternates.
+    serpentine: bool,
+    /// Whether the direction of the next row is flipped due to the serpentine order.
+    flip_row: bool,
 }
 
 impl OptimalIterator {
     /// Creates a new iterator from the specified axis-aligned (i.e., unrotated) coordinates.
+    ///
+    /// The corners must form a rectangle, i.e. opposite edges must be parallel and of equal
+    /// length and adjacent edges must be orthogonal; skewed quads would silently produce
+    /// mismatched extents and clipping. This is asserted in debug builds.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         tl: Vector,
         tr: Vector,
@@ -37,12 +149,17 @@ impl OptimalIterator {
         x0: f64,
         y0: f64,
     ) -> Self {
-        let (sin, cos) = angle.sin_cos();
+        debug_assert!(
+            is_rectangle(tl, tr, bl, br),
+            "corners must form a rectangle"
+        );
+        let (sin, cos) = angle.sin_cos_snapped();
 
         // Parameters of the axis-aligned rectangle.
         let rect_width = (tr - tl).norm();
         let rect_height = (bl - tl).norm();
-        let extent = Vector::new(rect_width, rect_height);
+        let edge_lengths = Vector::new(rect_width, rect_height);
+        let extent = edge_lengths;
         let center = (tl + tr + bl + br) * 0.25;
 
         // Calculate the rotated rectangle.
@@ -70,8 +187,15 @@ impl OptimalIterator {
         let start_y = center.y - (y_count_half * dy) + y0;
         let y = ((tl.y - start_y) / dy).ceil() * dy + start_y;
 
+        // Quarter turns need no intersection tests.
+        const EPSILON: f64 = 1e-12;
+        let axis_aligned = sin.abs() < EPSILON || cos.abs() < EPSILON;
+
         Self {
             y,
+            row_y: y,
+            start_y: y,
+            first_y: y,
             min_x: tl.x,
             max_y: br.y,
             center,
@@ -82,7 +206,13 @@ impl OptimalIterator {
             rect_left,
             rect_bottom,
             rect_right,
+            edge_lengths,
             x_iter: None,
+            axis_aligned,
+            boundary: Boundary::default(),
+            corner: Corner::default(),
+            serpentine: false,
+            flip_row: false,
         }
     }
 
@@ -92,44 +222,304 @@ impl OptimalIterator {
         &self.center
     }
 
-    /// Finds the intersection point that is furthest from the specified line's origin,
-    /// assuming the line's origin already is an intersection point.
-    fn find_intersections(&self, ray: &Line) -> Option<(Vector, Vector)> {
-        let mut min = f64::INFINITY;
-        let mut max = f64::NEG_INFINITY;
+    /// Returns whether nodes on the end of a row (or the last row) are produced.
+    #[inline(always)]
+    pub const fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    /// Sets whether nodes on the end of a row (or the last row) are produced.
+    ///
+    /// This restarts the iteration from the beginning.
+    pub fn set_boundary(&mut self, boundary: Boundary) {
+        self.boundary = boundary;
+        self.reset();
+    }
+
+    /// Returns the corner at which the iteration starts.
+    #[inline(always)]
+    pub const fn start_corner(&self) -> Corner {
+        self.corner
+    }
+
+    /// Sets the corner at which the iteration starts.
+    ///
+    /// This restarts the iteration from the beginning.
+    pub fn set_start_corner(&mut self, corner: Corner) {
+        self.corner = corner;
+        self.reset();
+    }
+
+    /// Returns whether the direction of the rows alternates.
+    #[inline(always)]
+    pub const fn serpentine(&self) -> bool {
+        self.serpentine
+    }
 
-        let width = self.extent.x;
-        let height = self.extent.y;
+    /// Sets whether the direction of the rows alternates, starting with the direction
+    /// given by the start corner.
+    ///
+    /// This restarts the iteration from the beginning.
+    pub fn set_serpentine(&mut self, serpentine: bool) {
+        self.serpentine = serpentine;
+        self.reset();
+    }
 
-        let top = ray.calculate_intersection_t(&self.rect_top, width);
-        let bottom = ray.calculate_intersection_t(&self.rect_bottom, width);
-        let left = ray.calculate_intersection_t(&self.rect_left, height);
-        let right = ray.calculate_intersection_t(&self.rect_right, height);
+    /// Restarts the iteration from the first row according to the start corner.
+    pub fn reset(&mut self) {
+        let mut y = self.start_y;
+        if self.corner.is_bottom() {
+            if self.boundary.exceeds(y, self.max_y) {
+                // There are no rows; start past the end.
+                y -= self.delta.y;
+            } else {
+                while !self.boundary.exceeds(y + self.delta.y, self.max_y) {
+                    y += self.delta.y;
+                }
+            }
+        }
+
+        self.y = y;
+        self.row_y = y;
+        self.first_y = y;
+        self.x_iter = None;
+        self.flip_row = false;
+    }
 
-        if let Some(t) = top {
-            min = min.min(t);
-            max = max.max(t);
+    /// Determines whether the row at the specified Y coordinate lies past the last row.
+    #[inline(always)]
+    fn is_past_end(&self, y: f64) -> bool {
+        if self.corner.is_bottom() {
+            // Allow for rounding errors accumulated while stepping upwards.
+            y < self.start_y - self.delta.y * 0.5
+        } else {
+            self.boundary.exceeds(y, self.max_y)
         }
+    }
 
-        if let Some(t) = bottom {
-            min = min.min(t);
-            max = max.max(t);
+    /// Returns the distance between the Y coordinates of consecutively visited rows.
+    #[inline(always)]
+    fn row_step(&self) -> f64 {
+        if self.corner.is_bottom() {
+            -self.delta.y
+        } else {
+            self.delta.y
         }
+    }
 
-        if let Some(t) = left {
-            min = min.min(t);
-            max = max.max(t);
+    /// Returns the extent of the axis-aligned bounding box of the rotated rectangle.
+    #[inline(always)]
+    pub const fn extent(&self) -> &Vector {
+        &self.extent
+    }
+}
+
+impl OptimalIterator {
+    /// Advances the iterator by an entire row, returning the row's Y coordinate
+    /// and an iterator over its X coordinates.
+    ///
+    /// If the current row was only partially consumed, its remainder is returned first.
+    pub fn next_row(&mut self) -> Option<(f64, OptimalXIterator)> {
+        if let Some(iter) = self.x_iter.take() {
+            return Some((self.row_y, iter));
         }
 
-        if let Some(t) = right {
-            min = min.min(t);
-            max = max.max(t);
+        loop {
+            if self.is_past_end(self.y) {
+                return None;
+            }
+
+            let y = self.y;
+            self.y += self.row_step();
+
+            if let Some(iter) = self.row_at(y, self.flip_row) {
+                if self.serpentine && iter.len() > 0 {
+                    self.flip_row = !self.flip_row;
+                }
+                return Some((y, iter));
+            }
         }
+    }
+
+    /// Returns an iterator over the remaining rows without advancing this iterator.
+    ///
+    /// If the current row was only partially consumed, its remainder is returned first.
+    pub fn remaining_rows(&self) -> impl Iterator<Item = (f64, OptimalXIterator)> + '_ {
+        let current = self.x_iter.clone().map(|iter| (self.row_y, iter));
+        let mut y = self.y;
+        let mut flip_row = self.flip_row;
+        let rows = std::iter::from_fn(move || loop {
+            if self.is_past_end(y) {
+                return None;
+            }
 
-        if min.is_finite() && max.is_finite() {
-            Some((ray.project_out(min), ray.project_out(max)))
+            let row_y = y;
+            y += self.row_step();
+
+            if let Some(iter) = self.row_at(row_y, flip_row) {
+                if self.serpentine && iter.len() > 0 {
+                    flip_row = !flip_row;
+                }
+                return Some((row_y, iter));
+            }
+        });
+        current.into_iter().chain(rows)
+    }
+
+    /// Determines the fraction of rows already visited, in range `0..=1`.
+    ///
+    /// This is derived from the Y coordinate of the current row alone and thus is only
+    /// an approximation of the fraction of coordinates already produced.
+    pub fn progress(&self) -> f64 {
+        let current = if self.x_iter.is_some() {
+            self.row_y
         } else {
-            None
+            self.y
+        };
+
+        let end = if self.corner.is_bottom() {
+            self.start_y
+        } else {
+            self.max_y
+        };
+
+        let span = end - self.first_y;
+        if span * self.row_step() <= 0.0 {
+            let is_done = self.x_iter.is_none() && self.is_past_end(self.y);
+            return if is_done { 1.0 } else { 0.0 };
+        }
+
+        ((current - self.first_y) / span).clamp(0.0, 1.0)
+    }
+
+    /// Determines the total number of rows, regardless of the progress of the iteration.
+    ///
+    /// This includes rows that do not contain any coordinates.
+    pub fn row_count(&self) -> usize {
+        let (start, end, dy) = (self.start_y, self.max_y, self.delta.y);
+        if self.boundary.exceeds(start, end) {
+            return 0;
+        }
+
+        // Correct for rounding errors in the division.
+        let mut count = ((end - start) / dy).floor() as usize + 1;
+        while count > 1 && self.boundary.exceeds(start + (count - 1) as f64 * dy, end) {
+            count -= 1;
+        }
+        while !self.boundary.exceeds(start + count as f64 * dy, end) {
+            count += 1;
+        }
+        count
+    }
+
+    /// Determines the number of remaining coordinates without advancing this iterator.
+    pub fn count_remaining(&self) -> usize {
+        self.remaining_rows().map(|(_, iter)| iter.len()).sum()
+    }
+
+    /// Advances the iterator by an entire row, returning the start and end point
+    /// of the row's intersection with the rotated rectangle.
+    ///
+    /// Any remaining coordinates of the current row are discarded.
+    pub fn next_span(&mut self) -> Option<(Vector, Vector)> {
+        self.x_iter = None;
+
+        loop {
+            if self.is_past_end(self.y) {
+                return None;
+            }
+
+            let y = self.y;
+            self.y += self.row_step();
+
+            if let Some(span) = self.span_at(y) {
+                return Some(span);
+            }
+        }
+    }
+
+    /// Returns the Y coordinate of the topmost row.
+    #[inline(always)]
+    pub const fn first_row_y(&self) -> f64 {
+        self.start_y
+    }
+
+    /// Determines whether the row at the specified Y coordinate lies below the bottommost row.
+    #[inline(always)]
+    pub fn is_below_last_row(&self, y: f64) -> bool {
+        self.boundary.exceeds(y, self.max_y)
+    }
+
+    /// Constructs the iterator over the X coordinates of the row at the specified Y coordinate,
+    /// optionally flipping the direction given by the start corner.
+    pub fn row_at(&self, y: f64, flip: bool) -> Option<OptimalXIterator> {
+        let (start, end) = self.span_at(y)?;
+        Some(
+            OptimalXIterator::new(
+                self.center,
+                self.extent,
+                start,
+                end,
+                self.delta.x,
+                self.offset.x,
+                self.boundary,
+            )
+            .with_reversed(self.corner.is_right() != flip),
+        )
+    }
+
+    /// Performs the intersection tests of the row at the specified Y coordinate with the
+    /// edges of the rotated rectangle, reporting the individual results.
+    ///
+    /// The tests are performed even for quarter turns, which bypass them during iteration.
+    pub fn debug_row(&self, y: f64) -> RowDebug {
+        let row_start = Vector::new(self.min_x, y);
+        let row_end = Vector::new(self.min_x + self.extent.x, y);
+        let ray = Line::from_points(row_start, &row_end);
+        let [top, left, bottom, right] = edge_intersections(
+            &ray,
+            [
+                &self.rect_top,
+                &self.rect_left,
+                &self.rect_bottom,
+                &self.rect_right,
+            ],
+            &self.edge_lengths,
+        );
+
+        let valid = || [top, left, bottom, right].into_iter().flatten();
+        RowDebug {
+            y,
+            top,
+            left,
+            bottom,
+            right,
+            min_t: valid().reduce(f64::min),
+            max_t: valid().reduce(f64::max),
+        }
+    }
+
+    /// Determines the start and end point of the intersection of the row
+    /// at the specified Y coordinate with the rotated rectangle.
+    fn span_at(&self, y: f64) -> Option<(Vector, Vector)> {
+        // Obtain the rows.
+        let x = self.min_x;
+        let row_start = Vector::new(x, y);
+        let row_end = Vector::new(x + self.extent.x, y);
+
+        // Determine the intersection of the ray from the given row with the rectangle.
+        // Degenerate rows (i.e., of zero extent) are skipped.
+        if self.axis_aligned {
+            Some((row_start, row_end))
+        } else {
+            let ray = Line::try_from_points(row_start, &row_end)?;
+            let edges = [
+                &self.rect_top,
+                &self.rect_left,
+                &self.rect_bottom,
+                &self.rect_right,
+            ];
+            find_intersections(&ray, edges, &self.edge_lengths)
         }
     }
 }
@@ -139,44 +529,111 @@ impl Iterator for OptimalIterator {
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            if self.y > self.max_y {
-                return None;
-            }
-
             if let Some(iter) = self.x_iter.as_mut() {
                 if let Some(x) = iter.next() {
-                    return Some(Vector::new(x, self.y));
+                    return Some(Vector::new(x, self.row_y));
                 }
 
-                self.y += self.delta.y;
+                self.x_iter = None;
             }
 
-            // Obtain the rows.
-            let x = self.min_x;
-            let row_start = Vector::new(x, self.y);
-            let row_end = Vector::new(x + self.extent.x, self.y);
-
-            // Determine the intersection of the ray from the given row with the rectangle.
-            let ray = Line::from_points(row_start, &row_end);
-            if let Some((start, end)) = self.find_intersections(&ray) {
-                self.x_iter = Some(OptimalXIterator::new(
-                    self.center,
-                    self.extent,
-                    start,
-                    end,
-                    self.delta.x,
-                    self.offset.x,
-                ));
-            }
+            let (y, iter) = self.next_row()?;
+            self.row_y = y;
+            self.x_iter = Some(iter);
         }
     }
 }
 
+/// Determines whether the corners form a rectangle, within a tolerance relative to its size.
+fn is_rectangle(tl: Vector, tr: Vector, bl: Vector, br: Vector) -> bool {
+    const EPSILON: f64 = 1e-9;
+    let top = tr - tl;
+    let left = bl - tl;
+    let scale = top.norm_sq().max(left.norm_sq());
+    (br - bl - top).norm_sq() <= EPSILON * scale && top.dot(&left).abs() <= EPSILON * scale
+}
+
+/// Determines the horizontal span in which the row at the specified Y coordinate
+/// intersects a (rotated) rectangle, or `None` if the row misses the rectangle.
+///
+/// ## Arguments
+/// * `corners` - The corners of the rectangle, in order along its perimeter
+///   (e.g. top left, top right, bottom right, bottom left).
+/// * `y` - The Y coordinate of the row.
+pub fn rectangle_row_span(corners: &[Vector; 4], y: f64) -> Option<(f64, f64)> {
+    let (min, max) = corners
+        .iter()
+        .fold((corners[0], corners[0]), |(min, max), corner| {
+            (
+                Vector::new(min.x.min(corner.x), min.y.min(corner.y)),
+                Vector::new(max.x.max(corner.x), max.y.max(corner.y)),
+            )
+        });
+    if y < min.y || y > max.y {
+        return None;
+    }
+
+    let [a, b, c, d] = *corners;
+    let top = Line::try_from_points(b, &a)?;
+    let left = Line::try_from_points(a, &d)?;
+    let bottom = Line::try_from_points(d, &c)?;
+    let right = Line::try_from_points(b, &c)?;
+
+    let ray = Line::try_from_points(Vector::new(min.x, y), &Vector::new(max.x, y))?;
+    let edge_lengths = Vector::new((a - b).norm(), (d - a).norm());
+    let (start, end) = find_intersections(&ray, [&top, &left, &bottom, &right], &edge_lengths)?;
+    Some((start.x, end.x))
+}
+
+/// Finds the nearest and furthest intersection points of a ray with the edges of a rectangle,
+/// given in order top, left, bottom and right. The `lengths` are those of the
+/// horizontal (top, bottom) and vertical (left, right) edges.
+fn find_intersections(ray: &Line, edges: [&Line; 4], lengths: &Vector) -> Option<(Vector, Vector)> {
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+
+    for t in edge_intersections(ray, edges, lengths)
+        .into_iter()
+        .flatten()
+    {
+        min = min.min(t);
+        max = max.max(t);
+    }
+
+    if min.is_finite() && max.is_finite() {
+        Some((ray.project_out(min), ray.project_out(max)))
+    } else {
+        None
+    }
+}
+
+/// Determines the intersection parameters of a ray with the edges of a rectangle,
+/// given and returned in order top, left, bottom and right.
+fn edge_intersections(ray: &Line, edges: [&Line; 4], lengths: &Vector) -> [Option<f64>; 4] {
+    let [top, left, bottom, right] = edges;
+    let width = lengths.x;
+    let height = lengths.y;
+    [
+        ray.calculate_intersection_t(top, width),
+        ray.calculate_intersection_t(left, height),
+        ray.calculate_intersection_t(bottom, width),
+        ray.calculate_intersection_t(right, height),
+    ]
+}
+
 /// Iterator for x coordinates along a ray
+///
+/// The coordinates are integer multiples of `dx` away from the (offset) center and lie within
+/// the row's start and end, inclusive. For rows shorter than `dx`, this yields at most one
+/// coordinate: the one nearest to the center, if it lies within the row.
+#[derive(Debug, Clone)]
 pub struct OptimalXIterator {
-    x: f64,
+    start_x: f64,
     dx: f64,
-    row_end: f64,
+    index: usize,
+    count: usize,
+    /// Whether the coordinates are visited from right to left.
+    reversed: bool,
 }
 
 impl OptimalXIterator {
@@ -187,6 +644,7 @@ impl OptimalXIterator {
         row_end: Vector,
         dx: f64,
         x0: f64,
+        boundary: Boundary,
     ) -> Self {
         // Determine the first x coordinate along the row that is
         // an integer multiple of dx away from the center and larger
@@ -195,24 +653,172 @@ impl OptimalXIterator {
         let start_x = center.x - (x_count_half * dx) + x0;
         let x = ((row_start.x - start_x) / dx).ceil() * dx + start_x;
 
+        // Determine the number of coordinates not exceeding the end of the row,
+        // correcting for rounding errors in the division.
+        let row_end = row_end.x;
+        let mut count = if boundary.exceeds(x, row_end) {
+            0
+        } else {
+            ((row_end - x) / dx).floor() as usize + 1
+        };
+        while count > 0 && boundary.exceeds(x + (count - 1) as f64 * dx, row_end) {
+            count -= 1;
+        }
+        while !boundary.exceeds(x + count as f64 * dx, row_end) {
+            count += 1;
+        }
+
         Self {
-            x,
+            start_x: x,
             dx,
-            row_end: row_end.x,
+            index: 0,
+            count,
+            reversed: false,
+        }
+    }
+}
+
+impl OptimalXIterator {
+    /// Sets whether the coordinates are visited from right to left.
+    pub fn with_reversed(mut self, reversed: bool) -> Self {
+        self.reversed = reversed;
+        self
+    }
+
+    /// Returns whether the coordinates are visited from right to left.
+    #[inline(always)]
+    pub const fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    /// Returns the `n`-th remaining X coordinate without advancing the iterator.
+    pub fn get(&self, n: usize) -> Option<f64> {
+        let index = self.index + n;
+        if index < self.count {
+            Some(self.x_at(index))
+        } else {
+            None
         }
     }
+
+    /// Determines the sum of the remaining X coordinates without advancing the iterator.
+    pub fn sum_remaining(&self) -> f64 {
+        let remaining = self.count - self.index;
+        let first = if self.reversed { 0 } else { self.index };
+        let n = remaining as f64;
+        let first = first as f64;
+        let last = first + n - 1.0;
+        n * self.start_x + self.dx * n * (first + last) * 0.5
+    }
+
+    /// Determines the X coordinate visited at the specified index.
+    #[inline(always)]
+    fn x_at(&self, index: usize) -> f64 {
+        let column = if self.reversed {
+            self.count - 1 - index
+        } else {
+            index
+        };
+        self.start_x + column as f64 * self.dx
+    }
 }
 
 impl Iterator for OptimalXIterator {
     type Item = f64;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let x = self.x;
-        if x > self.row_end {
+        if self.index >= self.count {
             return None;
         }
 
-        self.x += self.dx;
+        let x = self.x_at(self.index);
+        self.index += 1;
         Some(x)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.count - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for OptimalXIterator {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iterator(width: f64, height: f64, degrees: f64) -> OptimalIterator {
+        OptimalIterator::new(
+            Vector::new(0.0, 0.0),
+            Vector::new(width, 0.0),
+            Vector::new(0.0, height),
+            Vector::new(width, height),
+            Angle::from_degrees(degrees),
+            7.0,
+            5.0,
+            1.0,
+            2.0,
+        )
+    }
+
+    #[test]
+    fn test_axis_aligned_fast_path() {
+        for degrees in [0.0, 90.0] {
+            let fast = iterator(10240.0, 128.0, degrees);
+            assert!(fast.axis_aligned);
+
+            let mut slow = iterator(10240.0, 128.0, degrees);
+            slow.axis_aligned = false;
+
+            let fast: Vec<_> = fast.collect();
+            let slow: Vec<_> = slow.collect();
+            assert!(!fast.is_empty());
+            assert_eq!(fast.len(), slow.len());
+            for (a, b) in fast.iter().zip(&slow) {
+                assert!((*a - *b).norm() < 1e-9);
+            }
+        }
+
+        assert!(!iterator(10240.0, 128.0, 45.0).axis_aligned);
+    }
+
+    #[test]
+    fn test_rectangle_row_span() {
+        // A square of side length 2, rotated by 45° around the origin.
+        let r = 2.0_f64.sqrt();
+        let corners = [
+            Vector::new(0.0, -r),
+            Vector::new(r, 0.0),
+            Vector::new(0.0, r),
+            Vector::new(-r, 0.0),
+        ];
+
+        let (start, end) = rectangle_row_span(&corners, 0.0).expect("row intersects");
+        assert!((start + r).abs() < 1e-9);
+        assert!((end - r).abs() < 1e-9);
+
+        let (start, end) = rectangle_row_span(&corners, 0.5).expect("row intersects");
+        assert!((start + r - 0.5).abs() < 1e-9);
+        assert!((end - r + 0.5).abs() < 1e-9);
+
+        assert_eq!(rectangle_row_span(&corners, 2.0), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "corners must form a rectangle")]
+    fn test_skewed_quad() {
+        OptimalIterator::new(
+            Vector::new(0.0, 0.0),
+            Vector::new(10.0, 0.0),
+            Vector::new(2.0, 10.0),
+            Vector::new(12.0, 10.0),
+            Angle::from_degrees(15.0),
+            1.0,
+            1.0,
+            0.0,
+            0.0,
+        );
+    }
 }
@@ -1,21 +1,37 @@
 //! Contains the [`Vector`] type.
 
-use crate::Angle;
+use crate::inner::float::Float;
+use crate::{Angle, CachedAngle};
 use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
 
 #[derive(Debug, Default, Copy, Clone, PartialOrd, PartialEq)]
-pub struct Vector {
-    pub x: f64,
-    pub y: f64,
+pub struct Vector<T = f64> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Vector {
+impl<T> Vector<T> {
     /// Constructs a new vector from the specified coordinates.
     #[inline(always)]
-    pub const fn new(x: f64, y: f64) -> Self {
+    pub const fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
 
+    /// Constructs a new vector from an array of X and Y coordinates, in that order.
+    #[inline(always)]
+    pub fn from_array(xy: [T; 2]) -> Self {
+        let [x, y] = xy;
+        Self::new(x, y)
+    }
+
+    /// Converts this [`Vector`] into an array of X and Y coordinates, in that order.
+    #[inline(always)]
+    pub fn to_array(self) -> [T; 2] {
+        [self.x, self.y]
+    }
+}
+
+impl Vector<f64> {
     /// Rounds the coordinates to the specified number of decimals.
     /// This simplifies testing.
     pub fn round(&self, decimals: u32) -> Self {
@@ -26,59 +42,70 @@ impl Vector {
         }
     }
 
+    /// Rotates the vector counterclockwise by the specified angle.
+    pub fn rotate(&self, angle: Angle) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        self.rotate_with(sin, cos)
+    }
+
+    /// Rotates the vector counterclockwise by the specified angle,
+    /// using its precomputed sine and cosine.
+    #[inline(always)]
+    pub fn rotate_cached(&self, angle: &CachedAngle) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        self.rotate_with(sin, cos)
+    }
+
+    /// Rotates the vector counterclockwise by the specified angle.
+    pub fn rotate_around(&self, pivot: &Self, angle: Angle) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        self.rotate_around_with(pivot, sin, cos)
+    }
+}
+
+impl<T: Float> Vector<T> {
     /// Calculates the squared euclidean norm of the vector.
     #[inline(always)]
-    pub fn norm_sq(&self) -> f64 {
+    pub fn norm_sq(&self) -> T {
         self.x * self.x + self.y * self.y
     }
 
     /// Calculates the euclidean norm of the vector.
     #[inline(always)]
-    pub fn norm(&self) -> f64 {
+    pub fn norm(&self) -> T {
         self.norm_sq().sqrt()
     }
 
-    /// Calculates the euclidean norm of the vector.
+    /// Scales the vector to unit length.
+    ///
+    /// For (near) zero vectors this produces `NaN` or infinite coordinates;
+    /// use [`Vector::try_normalized`] if the vector may be zero.
     #[inline(always)]
     pub fn normalized(&self) -> Self {
         *self / self.norm()
     }
 
-    /// Rotates the vector counterclockwise by the specified angle.
-    pub fn rotate(&self, angle: Angle) -> Self {
-        let (sin, cos) = angle.sin_cos();
-        Self {
-            x: self.x * cos - self.y * sin,
-            y: self.x * sin + self.y * cos,
+    /// Scales the vector to unit length, or returns `None` if the vector is (near) zero.
+    #[inline(always)]
+    pub fn try_normalized(&self) -> Option<Self> {
+        let norm = self.norm();
+        if norm > T::EPSILON {
+            Some(*self / norm)
+        } else {
+            None
         }
     }
 
     /// Rotates the vector counterclockwise by the specified angle expressed as its sine and cosine.
-    pub fn rotate_with(&self, sin: f64, cos: f64) -> Self {
+    pub fn rotate_with(&self, sin: T, cos: T) -> Self {
         Self {
             x: self.x * cos - self.y * sin,
             y: self.x * sin + self.y * cos,
         }
     }
 
-    /// Rotates the vector counterclockwise by the specified angle.
-    pub fn rotate_around(&self, pivot: &Self, angle: Angle) -> Self {
-        let (sin, cos) = angle.sin_cos();
-
-        let x0 = self.x - pivot.x;
-        let y0 = self.y - pivot.y;
-
-        let x = x0 * cos - y0 * sin;
-        let y = x0 * sin + y0 * cos;
-
-        Self {
-            x: x + pivot.x,
-            y: y + pivot.y,
-        }
-    }
-
     /// Rotates the vector counterclockwise by the specified angle expressed as its sine and cosine.
-    pub fn rotate_around_with(&self, pivot: &Self, sin: f64, cos: f64) -> Self {
+    pub fn rotate_around_with(&self, pivot: &Self, sin: T, cos: T) -> Self {
         let x0 = self.x - pivot.x;
         let y0 = self.y - pivot.y;
 
@@ -100,33 +127,107 @@ impl Vector {
         }
     }
 
+    /// Rotates the vector 90° counterclockwise without using trigonometry,
+    /// i.e. exactly. This is the same as [`Vector::orthogonal`].
+    #[inline(always)]
+    pub fn rotate90(&self) -> Self {
+        self.orthogonal()
+    }
+
+    /// Rotates the vector 180° without using trigonometry, i.e. exactly.
+    #[inline(always)]
+    pub fn rotate180(&self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+
+    /// Rotates the vector 270° counterclockwise (i.e., 90° clockwise)
+    /// without using trigonometry, i.e. exactly.
+    #[inline(always)]
+    pub fn rotate270(&self) -> Self {
+        Self {
+            x: self.y,
+            y: -self.x,
+        }
+    }
+
     /// Calculates the dot product of two vectors.
     #[inline(always)]
-    pub fn dot(&self, other: &Self) -> f64 {
+    pub fn dot(&self, other: &Self) -> T {
         self.x * other.x + self.y * other.y
     }
 
     /// Calculates the 2D cross product of two vectors.
     #[inline(always)]
-    pub fn cross(&self, other: &Vector) -> f64 {
+    pub fn cross(&self, other: &Self) -> T {
         self.x * other.y - self.y * other.x
     }
 
+    /// Calculates the perp dot product of two vectors, i.e. the dot product of the
+    /// [orthogonal](Vector::orthogonal) of this vector with the other one.
+    ///
+    /// This is the same as [`Vector::cross`]; it is positive if the other vector points
+    /// counterclockwise of this one, e.g. for determining the winding order of polygons.
+    #[inline(always)]
+    pub fn perp_dot(&self, other: &Self) -> T {
+        self.cross(other)
+    }
+
     /// Projects a vector at a given distance alongside a direction
     /// from the current origin.
     #[inline(always)]
-    pub fn project_out(&self, direction: &Vector, t: f64) -> Self {
+    pub fn project_out(&self, direction: &Self, t: T) -> Self {
         Self {
             x: self.x + direction.x * t,
             y: self.y + direction.y * t,
         }
     }
+
+    /// Determines whether the coordinates of both vectors differ by no more than the tolerance.
+    #[inline(always)]
+    pub fn approx_eq(&self, other: &Self, tolerance: T) -> bool {
+        (self.x - other.x).abs() <= tolerance && (self.y - other.y).abs() <= tolerance
+    }
+}
+
+/// Calculates the signed area of the triangle spanned by three points, which is positive
+/// if the points are in counterclockwise order and negative if they are in clockwise order.
+/// Collinear points span an area of zero.
+#[inline(always)]
+pub fn signed_triangle_area<T: Float>(a: Vector<T>, b: Vector<T>, c: Vector<T>) -> T {
+    (b - a).perp_dot(&(c - a)) / (T::ONE + T::ONE)
+}
+
+impl<T> From<(T, T)> for Vector<T> {
+    fn from(value: (T, T)) -> Self {
+        Self::new(value.0, value.1)
+    }
+}
+
+impl<T> From<Vector<T>> for (T, T) {
+    fn from(value: Vector<T>) -> Self {
+        (value.x, value.y)
+    }
+}
+
+impl<T> From<[T; 2]> for Vector<T> {
+    fn from(value: [T; 2]) -> Self {
+        Self::from_array(value)
+    }
+}
+
+impl<T> From<Vector<T>> for [T; 2] {
+    fn from(value: Vector<T>) -> Self {
+        value.to_array()
+    }
 }
 
-impl Add<Vector> for Vector {
-    type Output = Vector;
+impl<T: Float> Add<Vector<T>> for Vector<T> {
+    type Output = Vector<T>;
 
-    fn add(self, rhs: Vector) -> Self::Output {
+    fn add(self, rhs: Vector<T>) -> Self::Output {
         Self {
             x: self.x + rhs.x,
             y: self.y + rhs.y,
@@ -134,17 +235,17 @@ impl Add<Vector> for Vector {
     }
 }
 
-impl AddAssign<Vector> for Vector {
-    fn add_assign(&mut self, rhs: Vector) {
+impl<T: Float> AddAssign<Vector<T>> for Vector<T> {
+    fn add_assign(&mut self, rhs: Vector<T>) {
         self.x += rhs.x;
         self.y += rhs.y;
     }
 }
 
-impl Sub<Vector> for Vector {
-    type Output = Vector;
+impl<T: Float> Sub<Vector<T>> for Vector<T> {
+    type Output = Vector<T>;
 
-    fn sub(self, rhs: Vector) -> Self::Output {
+    fn sub(self, rhs: Vector<T>) -> Self::Output {
         Self {
             x: self.x - rhs.x,
             y: self.y - rhs.y,
@@ -152,17 +253,17 @@ impl Sub<Vector> for Vector {
     }
 }
 
-impl SubAssign<Vector> for Vector {
-    fn sub_assign(&mut self, rhs: Vector) {
+impl<T: Float> SubAssign<Vector<T>> for Vector<T> {
+    fn sub_assign(&mut self, rhs: Vector<T>) {
         self.x -= rhs.x;
         self.y -= rhs.y;
     }
 }
 
-impl Mul<f64> for Vector {
-    type Output = Vector;
+impl<T: Float> Mul<T> for Vector<T> {
+    type Output = Vector<T>;
 
-    fn mul(self, rhs: f64) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         Self {
             x: self.x * rhs,
             y: self.y * rhs,
@@ -170,18 +271,26 @@ impl Mul<f64> for Vector {
     }
 }
 
-impl Mul<Vector> for f64 {
-    type Output = Vector;
+impl Mul<Vector<f64>> for f64 {
+    type Output = Vector<f64>;
 
-    fn mul(self, rhs: Vector) -> Self::Output {
+    fn mul(self, rhs: Vector<f64>) -> Self::Output {
         rhs * self
     }
 }
 
-impl Div<f64> for Vector {
-    type Output = Vector;
+impl Mul<Vector<f32>> for f32 {
+    type Output = Vector<f32>;
 
-    fn div(self, rhs: f64) -> Self::Output {
+    fn mul(self, rhs: Vector<f32>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl<T: Float> Div<T> for Vector<T> {
+    type Output = Vector<T>;
+
+    fn div(self, rhs: T) -> Self::Output {
         Self {
             x: self.x / rhs,
             y: self.y / rhs,
@@ -189,7 +298,7 @@ impl Div<f64> for Vector {
     }
 }
 
-impl Neg for Vector {
+impl<T: Float> Neg for Vector<T> {
     type Output = Self;
 
     fn neg(self) -> Self::Output {
@@ -206,66 +315,84 @@ mod tests {
 
     //noinspection RsApproxConstant
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_normalize() {
-        assert_eq!(
-            Vector { x: 2.0, y: 2.0 }.normalized().round(4),
+        assert_vec_eq!(
+            Vector { x: 2.0, y: 2.0 }.normalized(),
             Vector {
                 x: 0.7071,
                 y: 0.7071
-            }
+            },
+            1e-4
+        );
+    }
+
+    #[test]
+    fn test_try_normalized() {
+        assert_eq!(Vector { x: 0.0, y: 0.0 }.try_normalized(), None);
+        assert_eq!(Vector { x: 1e-20, y: 0.0 }.try_normalized(), None);
+        assert_eq!(
+            Vector { x: 3.0, y: 4.0 }.try_normalized(),
+            Some(Vector { x: 0.6, y: 0.8 })
         );
     }
 
     #[test]
     fn test_rotate() {
         let vector = Vector { x: 1.0, y: 0.0 };
-        assert_eq!(
-            vector.rotate(Angle::from_degrees(0.0)).round(3),
+        assert_vec_eq!(
+            vector.rotate(Angle::from_degrees(0.0)),
             Vector { x: 1.0, y: 0.0 }
         );
-        assert_eq!(
-            vector.rotate(Angle::from_degrees(90.0)).round(3),
+        assert_vec_eq!(
+            vector.rotate(Angle::from_degrees(90.0)),
             Vector { x: 0.0, y: 1.0 }
         );
-        assert_eq!(
-            vector.rotate(Angle::from_degrees(180.0)).round(3),
+        assert_vec_eq!(
+            vector.rotate(Angle::from_degrees(180.0)),
             Vector { x: -1.0, y: 0.0 }
         );
-        assert_eq!(
-            vector.rotate(Angle::from_degrees(-90.0)).round(3),
+        assert_vec_eq!(
+            vector.rotate(Angle::from_degrees(-90.0)),
             Vector { x: 0.0, y: -1.0 }
         );
-        assert_eq!(
-            vector.rotate(Angle::from_degrees(45.0)).round(3),
-            Vector { x: 1.0, y: 1.0 }.normalized().round(3)
+        assert_vec_eq!(
+            vector.rotate(Angle::from_degrees(45.0)),
+            Vector { x: 1.0, y: 1.0 }.normalized()
         );
     }
 
+    #[test]
+    fn test_rotate_cached() {
+        let vector = Vector { x: 2.0, y: -1.0 };
+        for degrees in [0.0, 15.0, 45.0, 90.0, 180.0, -75.0] {
+            let angle = Angle::from_degrees(degrees);
+            assert_eq!(
+                vector.rotate_cached(&angle.precompute()),
+                vector.rotate(angle)
+            );
+        }
+    }
+
     #[test]
     fn test_rotate_around() {
         let vector = Vector { x: 1.0, y: 0.0 };
 
         // Zero rotation (around any point) results in no change.
-        assert_eq!(
-            vector
-                .rotate_around(&vector, Angle::from_degrees(0.0))
-                .round(3),
+        assert_vec_eq!(
+            vector.rotate_around(&vector, Angle::from_degrees(0.0)),
             Vector { x: 1.0, y: 0.0 }
         );
 
         // Any rotation around the point itself results in no change.
-        assert_eq!(
-            vector
-                .rotate_around(&vector, Angle::from_degrees(45.0))
-                .round(3),
+        assert_vec_eq!(
+            vector.rotate_around(&vector, Angle::from_degrees(45.0)),
             Vector { x: 1.0, y: 0.0 }
         );
 
         // Rotate around the specified pivot vector.
-        assert_eq!(
-            vector
-                .rotate_around(&Vector { x: 1.0, y: 1.0 }, Angle::from_degrees(90.0))
-                .round(3),
+        assert_vec_eq!(
+            vector.rotate_around(&Vector { x: 1.0, y: 1.0 }, Angle::from_degrees(90.0)),
             Vector { x: 2.0, y: 1.0 }
         );
     }
@@ -283,6 +410,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_quarter_turns() {
+        let vector = Vector { x: 1.0, y: 0.0 };
+        assert_eq!(vector.rotate90(), Vector { x: 0.0, y: 1.0 });
+        assert_eq!(vector.rotate180(), Vector { x: -1.0, y: 0.0 });
+        assert_eq!(vector.rotate270(), Vector { x: 0.0, y: -1.0 });
+
+        let vector = Vector { x: 0.3, y: -2.7 };
+        assert_eq!(vector.rotate90().rotate90(), vector.rotate180());
+        assert_eq!(vector.rotate180().rotate90(), vector.rotate270());
+        assert_eq!(vector.rotate270().rotate90(), vector);
+    }
+
     #[test]
     fn test_dot() {
         assert_eq!(
@@ -295,4 +435,43 @@ mod tests {
             5.0
         );
     }
+
+    #[test]
+    fn test_perp_dot() {
+        let x = Vector { x: 1.0, y: 0.0 };
+        let y = Vector { x: 0.0, y: 1.0 };
+        assert_eq!(x.perp_dot(&y), 1.0);
+        assert_eq!(y.perp_dot(&x), -1.0);
+        assert_eq!(x.perp_dot(&x), 0.0);
+    }
+
+    #[test]
+    fn test_signed_triangle_area() {
+        let a = Vector { x: 0.0, y: 0.0 };
+        let b = Vector { x: 4.0, y: 0.0 };
+        let c = Vector { x: 0.0, y: 3.0 };
+        assert_eq!(signed_triangle_area(a, b, c), 6.0);
+        assert_eq!(signed_triangle_area(a, c, b), -6.0);
+        assert_eq!(signed_triangle_area(a, b, Vector { x: 8.0, y: 0.0 }), 0.0);
+    }
+
+    #[test]
+    fn test_tuple_conversion() {
+        let vector = Vector::from((2.0, -3.0));
+        assert_eq!(vector, Vector { x: 2.0, y: -3.0 });
+
+        let (x, y): (f64, f64) = vector.into();
+        assert_eq!((x, y), (2.0, -3.0));
+    }
+
+    #[test]
+    fn test_array_conversion() {
+        let vector = Vector::from_array([2.0, -3.0]);
+        assert_eq!(vector, Vector { x: 2.0, y: -3.0 });
+        assert_eq!(vector.to_array(), [2.0, -3.0]);
+
+        assert_eq!(Vector::from([4.0, 5.0]), Vector { x: 4.0, y: 5.0 });
+        let xy: [f64; 2] = Vector { x: 4.0, y: 5.0 }.into();
+        assert_eq!(xy, [4.0, 5.0]);
+    }
 }
@@ -50,25 +50,92 @@
 //! }
 //! ```
 
+#[macro_use]
+mod macros;
+
 mod angle;
+mod blue_noise;
+mod corners;
+mod dedup;
+mod error;
+mod estimate;
+mod export;
+mod filtered;
+#[cfg(test)]
+mod fixtures;
+mod geometry;
 mod grid_coord;
+mod hull;
+#[cfg(feature = "image")]
+mod image_mask;
+mod indexed;
 pub mod inner;
+mod line_screen;
+mod mapped;
+mod ordering;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod peekable;
+mod region;
+mod rosette;
+#[cfg(feature = "simd")]
+mod simd;
+mod spec;
+mod stats;
+mod svg;
+mod transform;
+mod units;
+mod variable_rows;
+mod wedge;
 
-use crate::angle::AngleOps;
+use crate::dedup::SpatialHash;
 use crate::inner::vector::Vector;
-pub use angle::Angle;
-pub use grid_coord::GridCoord;
-pub use inner::optimal_iterator::OptimalIterator;
+pub use angle::{Angle, CachedAngle, UnitVectorAngle};
+pub use blue_noise::BlueNoiseIterator;
+pub use corners::CorneredGrid;
+pub use dedup::DedupMerge;
+pub use error::{GridError, PixelError};
+pub use estimate::estimate_circle_count;
+pub use filtered::FilteredGrid;
+pub use grid_coord::{AsCoords, GridCoord, Rounding, TupleCoords};
+pub use indexed::{IndexedCoord, IndexedGrid};
+pub use inner::optimal_iterator::{
+    rectangle_row_span, Boundary, Corner, OptimalIterator, RowDebug,
+};
+pub use line_screen::LineScreenIterator;
+pub use mapped::MappedGrid;
+#[cfg(feature = "rayon")]
+pub use parallel::ParallelGrid;
+pub use peekable::PeekableGrid;
+pub use region::{Circle, ClipRegion, ConvexPolygon, Ellipse, Polygon, Rectangle};
+pub use rosette::{rosette_quality, AngleWarning, CmykScreens, PairSeparation, RosetteReport};
+pub use spec::GridSpec;
+pub use stats::GridStats;
+use std::sync::Arc;
+pub use units::{lpi_to_spacing, spacing_to_lpi};
+pub use variable_rows::VariableRowGrid;
+pub use wedge::SpacingWedge;
 
 /// An iterator for positions on a rotated grid.
 pub struct GridPositionIterator {
+    /// The top-left corner of the (unrotated) rectangle.
+    origin: Vector,
     width: f64,
     height: f64,
     dx: f64,
     dy: f64,
+    x0: f64,
+    y0: f64,
+    alpha: Angle<f64>,
     inv_sin: f64,
     inv_cos: f64,
     inner: OptimalIterator,
+    /// The optional region restricting the emitted positions.
+    clip: Option<Arc<dyn ClipRegion + Send + Sync>>,
+    /// The column and row stride of the emitted lattice nodes.
+    stride: (usize, usize),
+    /// Whether the grid wraps around horizontally, e.g. on a cylinder.
+    wrap_x: bool,
 }
 
 impl GridPositionIterator {
@@ -78,10 +145,26 @@ impl GridPositionIterator {
     /// * `width` - The width of the grid. Must be positive.
     /// * `height` - The height of the grid. Must be positive.
     /// * `dx` - The spacing of grid elements along the (rotated) X axis.
+    ///   Must be positive and finite.
     /// * `dy` - The spacing of grid elements along the (rotated) Y axis.
+    ///   Must be positive and finite.
     /// * `x0` - The X offset of the first grid element.
     /// * `x1` - The Y offset of the first grid element.
     /// * `alpha` - The orientation of the grid. Must be in range 0..90°.
+    ///
+    /// ## Panics
+    /// Panics if the width or height is not positive, if the spacing is not positive and finite,
+    /// or if the angle is out of range.
+    /// Negative spacings are rejected rather than interpreted as a reversed scan direction;
+    /// use [`GridPositionIterator::with_start_corner`] to change the direction instead.
+    /// See [`GridPositionIterator::try_new`] for a non-panicking alternative.
+    ///
+    /// ## Narrow rectangles
+    /// The grid nodes are placed at integer multiples of the spacing away from the
+    /// (offset) center of the rectangle. If the rectangle is narrower than the spacing,
+    /// e.g. `width < dx` for an unrotated grid, each row contains at most one position:
+    /// the node nearest to the center if it lies within the rectangle, or none otherwise.
+    /// Nodes exactly on the rectangle's edges are included.
     pub fn new(
         width: f64,
         height: f64,
@@ -90,93 +173,2500 @@ impl GridPositionIterator {
         x0: f64,
         y0: f64,
         alpha: Angle<f64>,
+    ) -> Self {
+        Self::new_at(Vector::default(), width, height, dx, dy, x0, y0, alpha)
+    }
+
+    /// Creates a new iterator from vectors rather than scalars, which avoids
+    /// mixing up the order of the arguments.
+    ///
+    /// ## Arguments
+    /// * `size` - The width and height of the grid. Must be positive.
+    /// * `spacing` - The spacing of grid elements along the (rotated) X and Y axis.
+    /// * `offset` - The X and Y offset of the first grid element.
+    /// * `alpha` - The orientation of the grid. Must be in range 0..90°.
+    ///
+    /// See [`GridPositionIterator::new`] for details.
+    pub fn new_v(size: Vector, spacing: Vector, offset: Vector, alpha: Angle<f64>) -> Self {
+        Self::new(
+            size.x, size.y, spacing.x, spacing.y, offset.x, offset.y, alpha,
+        )
+    }
+
+    /// Creates a new iterator for a rectangle whose top-left corner is placed at the specified origin.
+    ///
+    /// The produced coordinates are expressed in the same (absolute) space as the origin.
+    /// See [`GridPositionIterator::new`] for a description of the remaining arguments.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_at(
+        origin: Vector,
+        width: f64,
+        height: f64,
+        dx: f64,
+        dy: f64,
+        x0: f64,
+        y0: f64,
+        alpha: Angle<f64>,
     ) -> Self {
         assert!(alpha.into_radians() >= 0.0);
         assert!(alpha.into_radians() <= std::f64::consts::FRAC_PI_2);
         assert!(width > 0.0);
         assert!(height > 0.0);
+        assert!(
+            dx.is_finite() && dx > 0.0 && dy.is_finite() && dy > 0.0,
+            "the spacing must be positive and finite"
+        );
 
-        let tl = Vector::new(0.0, 0.0);
-        let tr = Vector::new(width, 0.0);
-        let bl = Vector::new(0.0, height);
-        let br = Vector::new(width, height);
+        let tl = origin;
+        let tr = origin + Vector::new(width, 0.0);
+        let bl = origin + Vector::new(0.0, height);
+        let br = origin + Vector::new(width, height);
 
-        let alpha = alpha.normalize();
-        let (sin, cos) = alpha.sin_cos();
+        let alpha = alpha.normalize_grid();
+        let (sin, cos) = alpha.sin_cos_snapped();
 
         Self {
+            origin,
             width,
             height,
             dx,
             dy,
+            x0,
+            y0,
+            alpha,
             inv_sin: -sin,
             inv_cos: cos,
             inner: OptimalIterator::new(tl, tr, bl, br, alpha, dx, dy, x0, y0),
+            clip: None,
+            stride: (1, 1),
+            wrap_x: false,
         }
     }
 
-    /// Provides an estimated upper bound for the number of grid points.
-    /// This is only correct for unrotated grids; rotated grids produce smaller values.
-    fn estimate_max_grid_points(&self) -> usize {
-        let num_points_x = (self.width + self.dx) / self.dx;
-        let num_points_y = (self.height + self.dy) / self.dy;
-        (num_points_x * num_points_y).ceil() as _
+    /// Creates a new iterator for a rectangle given by its center and half extents.
+    ///
+    /// The produced coordinates are expressed in the same (absolute) space as the center.
+    ///
+    /// ## Arguments
+    /// * `center` - The center of the rectangle.
+    /// * `half_extent` - Half the width and height of the rectangle. Must be positive.
+    /// * `dx` - The spacing of grid elements along the (rotated) X axis.
+    /// * `dy` - The spacing of grid elements along the (rotated) Y axis.
+    /// * `x0` - The X offset of the first grid element.
+    /// * `y0` - The Y offset of the first grid element.
+    /// * `alpha` - The orientation of the grid. Must be in range 0..90°.
+    pub fn from_center(
+        center: Vector,
+        half_extent: Vector,
+        dx: f64,
+        dy: f64,
+        x0: f64,
+        y0: f64,
+        alpha: Angle<f64>,
+    ) -> Self {
+        Self::new_at(
+            center - half_extent,
+            2.0 * half_extent.x,
+            2.0 * half_extent.y,
+            dx,
+            dy,
+            x0,
+            y0,
+            alpha,
+        )
     }
-}
 
-impl Iterator for GridPositionIterator {
-    type Item = GridCoord;
+    /// Creates a new iterator whose uniform spacing is chosen such that the grid has
+    /// approximately `target` positions, e.g. for a consistent ink volume across rectangles
+    /// of different sizes.
+    ///
+    /// Since each grid cell covers an area of `spacing²`, the spacing is derived from the area
+    /// of the rectangle as `√(width·height / target)`, regardless of the orientation. The nodes
+    /// are anchored at the center of the rectangle. The actual count deviates from the target
+    /// by up to about `(width + height) / spacing` positions, depending on how the nodes line up
+    /// with the edges; this is largest for unrotated grids. For targets of at least 500 positions
+    /// in rectangles of moderate aspect ratios, the count is within 10% of the target.
+    ///
+    /// ## Arguments
+    /// * `width` - The width of the grid. Must be positive.
+    /// * `height` - The height of the grid. Must be positive.
+    /// * `target` - The approximate number of positions. Must be positive.
+    /// * `alpha` - The orientation of the grid. Must be in range 0..90°.
+    ///
+    /// ## Panics
+    /// Panics if the target is zero.
+    pub fn with_target_count(width: f64, height: f64, target: usize, alpha: Angle<f64>) -> Self {
+        assert!(target > 0, "target count must be positive");
+        let spacing = (width * height / target as f64).sqrt();
+        Self::new(width, height, spacing, spacing, 0.0, 0.0, alpha)
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(point) = self.inner.next() {
-            let x = point.x;
-            let y = point.y;
-            let center = self.inner.center();
+    /// Creates a new iterator for a grid that tiles seamlessly across both axes,
+    /// e.g. for repeating halftone textures.
+    ///
+    /// The spacing is chosen as `width / nx` and `height / ny`, and the offsets are chosen
+    /// such that the corners of the rectangle lie at the centers of grid cells. When placing
+    /// copies of the rectangle next to each other, the positions continue across the seams
+    /// as if generated for one large rectangle.
+    ///
+    /// For rotated grids, this only works if the rotated lattice contains the translations
+    /// by the width and height of the rectangle, i.e. if the angle is commensurate with
+    /// the rectangle. This holds at 0° and, if `nx·height / width` and `ny·width / height`
+    /// are integers, at 90°; other angles are rarely representable exactly.
+    ///
+    /// ## Arguments
+    /// * `width` - The width of the grid. Must be positive.
+    /// * `height` - The height of the grid. Must be positive.
+    /// * `nx` - The number of grid cells along the width. Must be positive.
+    /// * `ny` - The number of grid cells along the height. Must be positive.
+    /// * `alpha` - The orientation of the grid. Must be in range 0..90°.
+    ///
+    /// ## Panics
+    /// Panics if `nx` or `ny` is zero, or if the lattice does not tile the rectangle
+    /// at the specified angle.
+    pub fn seamless(width: f64, height: f64, nx: usize, ny: usize, alpha: Angle<f64>) -> Self {
+        assert!(nx > 0 && ny > 0, "cell counts must be positive");
+        let (dx, dy) = (width / nx as f64, height / ny as f64);
+        let grid = Self::new(width, height, dx, dy, 0.0, 0.0, alpha);
+
+        // The seams are only invisible if moving by the width or height of the rectangle
+        // maps grid nodes onto grid nodes.
+        const EPSILON: f64 = 1e-9;
+        let corner = grid.to_lattice(Vector::new(0.0, 0.0));
+        let across_x = grid.to_lattice(Vector::new(width, 0.0)) - corner;
+        let across_y = grid.to_lattice(Vector::new(0.0, height)) - corner;
+        let is_integer = |value: f64| (value - value.round()).abs() < EPSILON;
+        assert!(
+            [across_x.x, across_x.y, across_y.x, across_y.y]
+                .iter()
+                .all(|&value| is_integer(value)),
+            "the grid angle is not commensurate with the rectangle"
+        );
+
+        // Shifting the offsets moves the corner to the center of its grid cell.
+        let x0 = ((corner.x - 0.5) * dx).rem_euclid(dx);
+        let y0 = ((corner.y - 0.5) * dy).rem_euclid(dy);
+        grid.with_offset(x0, y0)
+    }
+
+    /// Creates a new iterator for a screen frequency given in lines per inch.
+    ///
+    /// The spacing of grid elements along both axes is derived from the screen frequency
+    /// and the output resolution, see [`lpi_to_spacing`].
+    ///
+    /// ## Arguments
+    /// * `width` - The width of the grid in pixels. Must be positive.
+    /// * `height` - The height of the grid in pixels. Must be positive.
+    /// * `lpi` - The screen frequency in lines per inch. Must be positive.
+    /// * `dpi` - The resolution of the output in dots (pixels) per inch. Must be positive.
+    /// * `x0` - The X offset of the first grid element.
+    /// * `y0` - The Y offset of the first grid element.
+    /// * `alpha` - The orientation of the grid. Must be in range 0..90°.
+    ///
+    /// ## Panics
+    /// Panics if the screen frequency or resolution is not positive, or for the same
+    /// reasons as [`GridPositionIterator::new`].
+    pub fn from_lpi(
+        width: f64,
+        height: f64,
+        lpi: f64,
+        dpi: f64,
+        x0: f64,
+        y0: f64,
+        alpha: Angle<f64>,
+    ) -> Self {
+        assert!(
+            lpi > 0.0 && dpi > 0.0,
+            "the screen frequency and resolution must be positive"
+        );
+        let spacing = lpi_to_spacing(lpi, dpi);
+        Self::new(width, height, spacing, spacing, x0, y0, alpha)
+    }
+
+    /// Creates a new iterator, validating the arguments.
+    ///
+    /// See [`GridPositionIterator::new`] for a description of the arguments.
+    ///
+    /// ## Errors
+    /// Returns a [`GridError`] if the dimensions or spacings are not positive and finite,
+    /// or if the angle is not finite or outside the range 0..90°.
+    pub fn try_new(
+        width: f64,
+        height: f64,
+        dx: f64,
+        dy: f64,
+        x0: f64,
+        y0: f64,
+        alpha: Angle<f64>,
+    ) -> Result<Self, GridError> {
+        if !(width.is_finite() && width > 0.0 && height.is_finite() && height > 0.0) {
+            return Err(GridError::NonPositiveDimension);
+        }
+
+        if !(dx.is_finite() && dx > 0.0 && dy.is_finite() && dy > 0.0) {
+            return Err(GridError::NonPositiveSpacing);
+        }
+
+        let radians = alpha.into_radians();
+        if !radians.is_finite() {
+            return Err(GridError::NonFiniteAngle);
+        }
+
+        if !(0.0..=std::f64::consts::FRAC_PI_2).contains(&radians) {
+            return Err(GridError::AngleOutOfRange);
+        }
+
+        Ok(Self::new(width, height, dx, dy, x0, y0, alpha))
+    }
+
+    /// Creates a new iterator that only yields the positions within a crop window.
+    ///
+    /// The grid is generated for the full `outer` rectangle such that the lattice stays
+    /// anchored to the outer rectangle's center; only the positions that lie within
+    /// the axis-aligned `crop` window are produced. This differs from constructing a grid
+    /// on the crop window itself, which would align the lattice to the crop window's center.
+    ///
+    /// ## Arguments
+    /// * `outer` - The width and height of the full grid. Must be positive.
+    /// * `crop` - The minimum (top-left) and maximum (bottom-right) corner of the crop window.
+    /// * `dx` - The spacing of grid elements along the (rotated) X axis.
+    /// * `dy` - The spacing of grid elements along the (rotated) Y axis.
+    /// * `x0` - The X offset of the first grid element.
+    /// * `y0` - The Y offset of the first grid element.
+    /// * `alpha` - The orientation of the grid. Must be in range 0..90°.
+    pub fn new_cropped(
+        outer: (f64, f64),
+        crop: (Vector, Vector),
+        dx: f64,
+        dy: f64,
+        x0: f64,
+        y0: f64,
+        alpha: Angle<f64>,
+    ) -> Self {
+        let (width, height) = outer;
+        let mut grid = Self::new(width, height, dx, dy, x0, y0, alpha);
+        grid.clip = Some(Arc::new(Rectangle::new(crop.0, crop.1)));
+        grid
+    }
+
+    /// Creates a new iterator that only yields the positions within a simple polygon.
+    ///
+    /// The grid is generated over the polygon's axis-aligned bounding box and positions are
+    /// kept according to the even-odd rule, such that concave polygons are supported and the
+    /// winding order of the vertices does not matter. Positions on the polygon's edges are kept.
+    ///
+    /// ## Arguments
+    /// * `vertices` - The vertices of the polygon. Must contain at least three vertices.
+    /// * `dx` - The spacing of grid elements along the (rotated) X axis.
+    /// * `dy` - The spacing of grid elements along the (rotated) Y axis.
+    /// * `x0` - The X offset of the first grid element.
+    /// * `y0` - The Y offset of the first grid element.
+    /// * `alpha` - The orientation of the grid. Must be in range 0..90°.
+    pub fn new_in_polygon_general(
+        vertices: &[Vector],
+        dx: f64,
+        dy: f64,
+        x0: f64,
+        y0: f64,
+        alpha: Angle<f64>,
+    ) -> Self {
+        Self::new_clipped(&Polygon::new(vertices.to_vec()), dx, dy, x0, y0, alpha)
+    }
+
+    /// Creates a new iterator that only yields the positions within an arbitrary region.
+    ///
+    /// The grid is generated over the region's axis-aligned bounding box and positions are
+    /// kept if the region contains them. The region is cloned into the iterator.
+    ///
+    /// ## Arguments
+    /// * `region` - The region restricting the positions, e.g. a [`Circle`] or [`Ellipse`].
+    /// * `dx` - The spacing of grid elements along the (rotated) X axis.
+    /// * `dy` - The spacing of grid elements along the (rotated) Y axis.
+    /// * `x0` - The X offset of the first grid element.
+    /// * `y0` - The Y offset of the first grid element.
+    /// * `alpha` - The orientation of the grid. Must be in range 0..90°.
+    pub fn new_clipped<R>(region: &R, dx: f64, dy: f64, x0: f64, y0: f64, alpha: Angle<f64>) -> Self
+    where
+        R: ClipRegion + Clone + Send + Sync + 'static,
+    {
+        let (min, max) = region.bounds();
+        let extent = max - min;
+        let mut grid = Self::new_at(min, extent.x, extent.y, dx, dy, x0, y0, alpha);
+        grid.clip = Some(Arc::new(region.clone()));
+        grid
+    }
+
+    /// Creates a new iterator whose nodes lie exactly on pixel centers, i.e. at `(i + 0.5, j + 0.5)`
+    /// for integer `i` and `j`, avoiding sub-pixel drift that causes aliasing in dot patterns.
+    ///
+    /// To achieve this, the lattice vectors are snapped to integer pixel offsets: the angle is
+    /// approximated by the direction of the nearest integer vector of length `dx`, and the
+    /// spacings become integer multiples of that vector's length. The offset is chosen such that
+    /// a node lies on the pixel center nearest to the center of the rectangle.
+    ///
+    /// This trades accuracy of the screen angle and frequency for exact pixel alignment:
+    /// at 0° the spacings are merely rounded to whole pixels, but e.g. a spacing of `8` at 15°
+    /// becomes a spacing of about `8.25` at about 14.04°. Larger spacings approximate the
+    /// requested angle more closely. Use [`GridPositionIterator::lattice_vectors`] to obtain
+    /// the effective lattice.
+    ///
+    /// ## Arguments
+    /// * `width` - The width of the grid. Must be positive.
+    /// * `height` - The height of the grid. Must be positive.
+    /// * `dx` - The approximate spacing of grid elements along the (rotated) X axis.
+    /// * `dy` - The approximate spacing of grid elements along the (rotated) Y axis.
+    /// * `alpha` - The approximate orientation of the grid. Must be in range 0..90°.
+    ///
+    /// ## Panics
+    /// Panics if `dx` is too small to be approximated by a non-zero integer vector,
+    /// i.e. below about half a pixel.
+    pub fn new_pixel_aligned(width: f64, height: f64, dx: f64, dy: f64, alpha: Angle<f64>) -> Self {
+        fn gcd(a: u64, b: u64) -> u64 {
+            if b == 0 {
+                a
+            } else {
+                gcd(b, a % b)
+            }
+        }
+
+        let (sin, cos) = alpha.normalize_grid().sin_cos();
+        let a = (dx * cos).round().max(0.0) as u64;
+        let b = (dx * sin).round().max(0.0) as u64;
+        assert!(a + b > 0, "spacing is too small to be aligned to pixels");
+
+        // The shortest integer vector along the snapped direction.
+        let g = gcd(a, b);
+        let step = Vector::new((a / g) as f64, (b / g) as f64);
+        let dx = g as f64 * step.norm();
+        let dy = (dy / step.norm()).round().max(1.0) * step.norm();
+        let alpha = Angle::from_radians(step.y.atan2(step.x));
+
+        // Place a node on the pixel center nearest to the center of the rectangle.
+        let center = Vector::new(width * 0.5, height * 0.5);
+        let anchor = Vector::new(center.x.floor() + 0.5, center.y.floor() + 0.5);
+        let offset = anchor.rotate_around(&center, alpha) - center;
 
-            // Un-rotate the point.
-            let unrotated_x =
-                (x - center.x) * self.inv_cos - (y - center.y) * self.inv_sin + center.x;
-            let unrotated_y =
-                (x - center.x) * self.inv_sin + (y - center.y) * self.inv_cos + center.y;
+        Self::new(width, height, dx, dy, offset.x, offset.y, alpha)
+    }
+
+    /// Creates a new iterator that only yields the positions within a disk.
+    ///
+    /// The grid is generated over the disk's bounding square, such that the lattice is
+    /// anchored to the center of the disk. Positions on the circumference are kept.
+    /// See [`estimate_circle_count`] for an estimate of the number of positions.
+    ///
+    /// ## Arguments
+    /// * `center` - The center of the disk.
+    /// * `radius` - The radius of the disk. Must be positive.
+    /// * `dx` - The spacing of grid elements along the (rotated) X axis.
+    /// * `dy` - The spacing of grid elements along the (rotated) Y axis.
+    /// * `x0` - The X offset of the first grid element.
+    /// * `y0` - The Y offset of the first grid element.
+    /// * `alpha` - The orientation of the grid. Must be in range 0..90°.
+    pub fn new_in_circle(
+        center: Vector,
+        radius: f64,
+        dx: f64,
+        dy: f64,
+        x0: f64,
+        y0: f64,
+        alpha: Angle<f64>,
+    ) -> Self {
+        Self::new_clipped(&Circle::new(center, radius), dx, dy, x0, y0, alpha)
+    }
 
-            Some(GridCoord::new(unrotated_x, unrotated_y))
+    /// Anchors the grid such that a grid node lies exactly on the center of the rectangle.
+    ///
+    /// If enabled, the `x0` and `y0` offsets of the grid are discarded and the iteration
+    /// restarts from the beginning; otherwise, the iterator is returned unchanged.
+    pub fn with_center_anchored(self, center_anchored: bool) -> Self {
+        if center_anchored {
+            self.with_offset(0.0, 0.0)
         } else {
-            None
+            self
         }
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, Some(self.estimate_max_grid_points()))
+    /// Shifts the lattice such that a grid node lies exactly on the specified reference point,
+    /// e.g. a registration mark, while keeping the spacing and orientation of the grid.
+    ///
+    /// The `x0` and `y0` offsets are replaced by the smallest offsets (i.e., within half
+    /// the spacing) placing a node on the reference point. The reference point may lie
+    /// outside of the rectangle. The iteration restarts from the beginning; clip regions,
+    /// strides and other settings are retained.
+    pub fn align_to(&mut self, reference: Vector) {
+        let center = *self.inner.center();
+        let rotated = self.to_grid_space(reference);
+        let phase = |distance: f64, spacing: f64| distance - (distance / spacing).round() * spacing;
+        let x0 = phase(rotated.x - center.x, self.dx);
+        let y0 = phase(rotated.y - center.y, self.dy);
+        *self = self.with_offset(x0, y0);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Creates the dual grid, whose nodes lie at the centers of the cells of this grid,
+    /// e.g. to draw the gaps between the dots of a halftone.
+    ///
+    /// The lattice is shifted by half a cell along both axes in rotated grid space, i.e. by
+    /// `(dx/2, dy/2)`. The iteration restarts from the beginning; clip regions, strides and
+    /// other settings are retained.
+    pub fn dual(self) -> Self {
+        self.with_offset(self.x0 + 0.5 * self.dx, self.y0 + 0.5 * self.dy)
+    }
 
-    #[test]
-    fn test() {
-        const WIDTH: f64 = 10240.0;
-        const HEIGHT: f64 = 128.0;
-        const ANGLE: f64 = 45.0;
+    /// Restarts the iteration from the beginning, e.g. to count and then draw the
+    /// positions using the same instance. Clip regions, strides and other settings
+    /// are retained.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
 
-        for _ in 0..1000 {
-            let grid = GridPositionIterator::new(
-                WIDTH as _,
-                HEIGHT as _,
-                7.0,
-                7.0,
-                0.0,
-                0.0,
-                Angle::<f64>::from_degrees(ANGLE),
-            );
+    /// Determines the grid node closest to the specified point.
+    ///
+    /// The returned node lies on the (infinite) lattice of the grid and is not
+    /// necessarily within the rectangle or clip region.
+    pub fn nearest(&self, point: Vector) -> GridCoord {
+        let lattice = self.to_lattice(point);
+        self.lattice_point(Vector::new(lattice.x.round(), lattice.y.round()))
+    }
 
-            let mut count = 0;
-            for _ in grid.into_iter() {
-                count += 1;
+    /// Determines the coverage of a pixel by the dot at the nearest grid node, in range `0..=1`.
+    ///
+    /// The dots are disks of the specified radius centered on the grid nodes. Pixels within
+    /// the dot are fully covered, pixels outside of it are not covered at all; the coverage
+    /// falls off linearly across a one-pixel wide band at the dot's edge for anti-aliasing.
+    /// This evaluates the halftone screen per pixel in constant time without enumerating the grid.
+    pub fn dither_at(&self, pixel: Vector, dot_radius: f64) -> f64 {
+        let node = self.nearest(pixel);
+        let distance = (pixel - Vector::new(node.x, node.y)).norm();
+        (dot_radius + 0.5 - distance).clamp(0.0, 1.0)
+    }
+
+    /// Determines the largest dot radius at which dots on neighboring grid nodes do not overlap,
+    /// i.e. half the distance between the closest grid nodes.
+    ///
+    /// Since the lattice is rectangular, the closest nodes are neighbors along the axis of
+    /// smaller spacing, such that this is `0.5 · min(dx, dy)` regardless of the rotation.
+    /// At this radius, neighboring dots along that axis touch.
+    pub fn max_nonoverlap_radius(&self) -> f64 {
+        0.5 * self.dx.min(self.dy)
+    }
+
+    /// Determines the vector from the target to the closest remaining grid position without
+    /// advancing the iterator, e.g. to report the sub-pixel misregistration of a calibration
+    /// mark, or `None` if there are no remaining positions.
+    ///
+    /// Unlike [`GridPositionIterator::nearest`], only positions within the rectangle and clip
+    /// region are considered. Rows are visited in order of their distance to the target until
+    /// no closer position can be found.
+    pub fn registration_error(&self, target: Vector) -> Option<Vector> {
+        let rotated = self.to_grid_space(target);
+        let mut rows: Vec<_> = self.inner.remaining_rows().collect();
+        rows.sort_by(|(a, _), (b, _)| {
+            (a - rotated.y)
+                .abs()
+                .partial_cmp(&(b - rotated.y).abs())
+                .unwrap()
+        });
+
+        let mut nearest: Option<(f64, Vector)> = None;
+        for (y, xs) in rows {
+            let dy = y - rotated.y;
+            if nearest.map_or(false, |(distance_sq, _)| dy * dy >= distance_sq) {
+                break;
             }
 
-            assert!(count > 0);
+            for coord in xs.filter_map(|x| self.emit(Vector::new(x, y))) {
+                let error = Vector::new(coord.x - target.x, coord.y - target.y);
+                if nearest.map_or(true, |(distance_sq, _)| error.norm_sq() < distance_sq) {
+                    nearest = Some((error.norm_sq(), error));
+                }
+            }
+        }
+
+        nearest.map(|(_, error)| error)
+    }
+
+    /// Determines the exact number of remaining grid positions without
+    /// advancing the iterator.
+    ///
+    /// Unlike [`Iterator::size_hint`], this visits every remaining row of the grid.
+    pub fn exact_count(&self) -> usize {
+        if !self.is_filtered() {
+            return self.inner.count_remaining();
+        }
+
+        self.inner
+            .remaining_rows()
+            .map(|(y, xs)| xs.filter_map(|x| self.emit(Vector::new(x, y))).count())
+            .sum()
+    }
+
+    /// Determines whether there are no remaining grid positions without advancing the iterator,
+    /// e.g. to skip the setup for rectangles smaller than a grid cell.
+    ///
+    /// Rows are visited until the first position is found; since the positions of each row
+    /// are known analytically, this does not visit positions unless the grid is clipped or strided.
+    pub fn is_empty(&self) -> bool {
+        if !self.is_filtered() {
+            return self.inner.remaining_rows().all(|(_, xs)| xs.len() == 0);
+        }
+
+        self.inner
+            .remaining_rows()
+            .all(|(y, mut xs)| !xs.any(|x| self.emit(Vector::new(x, y)).is_some()))
+    }
+
+    /// Determines the number of remaining grid positions within the half-open sub-rectangle
+    /// `[min, max)` without advancing the iterator, e.g. to balance the work of rendering tiles.
+    ///
+    /// Since the positions of each row are evenly spaced, the range of positions within the
+    /// sub-rectangle is determined per row rather than per position unless the grid is
+    /// clipped or strided.
+    pub fn count_in_rect(&self, min: Vector, max: Vector) -> usize {
+        let inside = |coord: &GridCoord| {
+            min.x <= coord.x && coord.x < max.x && min.y <= coord.y && coord.y < max.y
+        };
+
+        if self.is_filtered() {
+            return self
+                .inner
+                .remaining_rows()
+                .map(|(y, xs)| {
+                    xs.filter_map(|x| self.emit(Vector::new(x, y)))
+                        .filter(inside)
+                        .count()
+                })
+                .sum();
+        }
+
+        let (forward, _) = self.lattice_vectors();
+        self.inner
+            .remaining_rows()
+            .map(|(y, xs)| {
+                let step = if xs.is_reversed() { -forward } else { forward };
+                let node = |k: usize| {
+                    let x = xs.get(k).expect("index is within the row");
+                    self.unrotate(Vector::new(x, y))
+                };
+
+                let len = xs.len();
+                if len == 0 {
+                    return 0;
+                }
+
+                // Solve `from <= start + k·step < to` for k along both axes.
+                let start = node(0);
+                let axes = [
+                    (start.x, step.x, min.x, max.x),
+                    (start.y, step.y, min.y, max.y),
+                ];
+                let (lo, hi) = axes.into_iter().fold(
+                    (f64::NEG_INFINITY, f64::INFINITY),
+                    |(lo, hi), (start, step, from, to)| {
+                        let (a, b) = if step.abs() < f64::EPSILON {
+                            if from <= start && start < to {
+                                (f64::NEG_INFINITY, f64::INFINITY)
+                            } else {
+                                (f64::INFINITY, f64::NEG_INFINITY)
+                            }
+                        } else if step > 0.0 {
+                            ((from - start) / step, (to - start) / step)
+                        } else {
+                            ((to - start) / step, (from - start) / step)
+                        };
+                        (lo.max(a), hi.min(b))
+                    },
+                );
+                if lo > hi {
+                    return 0;
+                }
+
+                // Widen the estimate to account for rounding errors, then shrink it
+                // to the positions that actually lie within the sub-rectangle.
+                let mut first = (lo.ceil() - 1.0).clamp(0.0, len as f64) as usize;
+                let mut end = (hi.floor() + 2.0).clamp(0.0, len as f64) as usize;
+                while first < end && !inside(&node(first)) {
+                    first += 1;
+                }
+                while end > first && !inside(&node(end - 1)) {
+                    end -= 1;
+                }
+                end - first
+            })
+            .sum()
+    }
+
+    /// Determines the total number of rows (scanlines) in rotated grid space, regardless
+    /// of the progress of the iteration, e.g. to preallocate row-grouped structures.
+    ///
+    /// This is computed from the geometry of the grid in constant time. Rows at the
+    /// corners of the rotated rectangle may not contain any positions; these are counted
+    /// nonetheless.
+    pub fn row_count(&self) -> usize {
+        self.inner.row_count()
+    }
+
+    /// Determines the approximate progress of the iteration as a fraction in range `0..=1`,
+    /// based on the current row in rotated grid space.
+    ///
+    /// This is cheap to compute, but does not account for the varying number of
+    /// positions per row; use [`GridPositionIterator::exact_count`] for exact numbers.
+    pub fn progress(&self) -> f64 {
+        self.inner.progress()
+    }
+
+    /// Only emits the grid nodes whose lattice indices are multiples of the specified strides,
+    /// e.g. every second column and row for a stride of `(2, 2)`.
+    ///
+    /// The lattice itself is unchanged, i.e. the emitted nodes are a subset of the
+    /// original nodes. The lattice indices are counted from the node at the (offset)
+    /// center of the rectangle.
+    ///
+    /// ## Panics
+    /// Panics if either stride is zero.
+    pub fn with_stride(mut self, col_stride: usize, row_stride: usize) -> Self {
+        assert!(col_stride > 0 && row_stride > 0);
+        self.stride = (col_stride, row_stride);
+        self
+    }
+
+    /// Sets whether grid nodes lying exactly on the end of a row (or the last row)
+    /// in rotated grid space are produced.
+    ///
+    /// With [`Boundary::Exclusive`], rows cover half-open intervals; for unrotated grids,
+    /// this ensures that adjacent tiles do not both produce the nodes on their shared seam.
+    pub fn with_boundary(mut self, boundary: Boundary) -> Self {
+        self.inner.set_boundary(boundary);
+        self
+    }
+
+    /// Sets whether the grid wraps around horizontally, e.g. when mapping the rectangle
+    /// onto a cylinder, such that the lattice is continuous across the seam at the left
+    /// and right edges of the rectangle.
+    ///
+    /// If enabled, the horizontal spacing is adjusted to the nearest spacing dividing the
+    /// width, i.e. `dx` for unrotated grids or `dy` for grids rotated by 90°, and nodes on
+    /// the right edge are skipped as they coincide with the nodes on the left edge.
+    /// The iteration restarts from the beginning.
+    ///
+    /// ## Panics
+    /// Panics if wrapping is enabled for a grid that is neither unrotated nor rotated by 90°,
+    /// since such lattices are not periodic along the X axis.
+    pub fn with_wrap_x(self, wrap_x: bool) -> Self {
+        if !wrap_x {
+            let mut grid = self;
+            grid.wrap_x = false;
+            return grid;
         }
+
+        const EPSILON: f64 = 1e-9;
+        let radians = self.alpha.into_radians();
+        let quarter_turn = (radians - std::f64::consts::FRAC_PI_2).abs() < EPSILON;
+        assert!(
+            radians.abs() < EPSILON || quarter_turn,
+            "wrapping requires an unrotated grid or a grid rotated by 90°"
+        );
+
+        let spacing = if quarter_turn { self.dy } else { self.dx };
+        let spacing = self.width / (self.width / spacing).round().max(1.0);
+        let (dx, dy) = if quarter_turn {
+            (self.dx, spacing)
+        } else {
+            (spacing, self.dy)
+        };
+
+        let mut grid = Self::new_at(
+            self.origin,
+            self.width,
+            self.height,
+            dx,
+            dy,
+            self.x0,
+            self.y0,
+            self.alpha,
+        );
+        grid.copy_settings(&self);
+        grid.wrap_x = true;
+        grid
+    }
+
+    /// Sets the corner of the rectangle in rotated grid space at which the iteration starts,
+    /// e.g. to lay down dots from a specific direction. The set of positions is unchanged.
+    ///
+    /// With [`Corner::TopLeft`] (the default), rows are visited from top to bottom and
+    /// each row from left to right, such that the first position is the leftmost position
+    /// of the top row. The other corners reverse the order of the rows and/or positions
+    /// accordingly, e.g. [`Corner::BottomRight`] starts at the rightmost position of the
+    /// bottom row. The iteration restarts from the beginning.
+    pub fn with_start_corner(mut self, corner: Corner) -> Self {
+        self.inner.set_start_corner(corner);
+        self
+    }
+
+    /// Reverses the direction of every other row, i.e. emits the positions in boustrophedon
+    /// (serpentine) order, e.g. to minimize the travel of a plotter. The set of positions
+    /// is unchanged.
+    ///
+    /// The first row is visited in the direction given by the start corner (see
+    /// [`GridPositionIterator::with_start_corner`]). Rows are counted in rotated grid space;
+    /// rows whose positions are all skipped due to a clip region or stride are counted
+    /// nonetheless. The iteration restarts from the beginning.
+    pub fn serpentine(mut self) -> Self {
+        self.inner.set_serpentine(true);
+        self
+    }
+
+    /// Varies the spacing of the rows, e.g. for perspective-corrected screens.
+    ///
+    /// Starting from the first row of the grid, the closure is queried for the spacing from
+    /// each row to the next, given the row's Y coordinate in rotated grid space; it thus
+    /// replaces `dy` for the row stepping. The positions within each row, the clip region and
+    /// the column stride are unaffected. The rows are visited from top to bottom, regardless
+    /// of the start corner, and the iteration starts from the first row.
+    ///
+    /// ## Panics
+    /// Panics if the grid has a row stride other than one, since rows are no longer counted
+    /// on the lattice. Iterating panics if the closure returns a spacing that is not positive.
+    pub fn with_row_spacing<F>(self, spacing: F) -> VariableRowGrid<F>
+    where
+        F: Fn(f64) -> f64,
+    {
+        assert_eq!(self.stride.1, 1, "row strides are not supported");
+        VariableRowGrid::new(self, spacing)
+    }
+
+    /// Thins out the grid to approximately `target` evenly spread positions, e.g. for previews.
+    ///
+    /// Column and row strides (see [`GridPositionIterator::with_stride`]) are derived from
+    /// the exact number of remaining positions, such that only the kept positions are
+    /// generated. Since the strides are integers, the number of positions only approximates
+    /// the target; for grids much larger than the target, it is typically within 10%.
+    /// If the grid has no more than `target` positions, all positions are kept.
+    pub fn sample_approx(self, target: usize) -> Self {
+        let count = self.exact_count();
+        if target == 0 || count <= target {
+            return self;
+        }
+
+        let ratio = count as f64 / target as f64;
+        let col_stride = ratio.sqrt().round().max(1.0);
+        let row_stride = (ratio / col_stride).round().max(1.0);
+        self.with_stride(col_stride as usize, row_stride as usize)
+    }
+
+    /// Attaches a sequential index to each emitted position, counting from zero.
+    ///
+    /// The index counts the emitted (i.e., non-clipped) positions; it does not describe
+    /// the position of the node on the lattice.
+    pub fn with_index(self) -> IndexedGrid {
+        IndexedGrid::new(self)
+    }
+
+    /// Emits the four corners of the rectangle before the grid positions, e.g. for meshers
+    /// that require the corners to be present.
+    ///
+    /// The corners are emitted in draw order (top left, top right, bottom right, bottom left).
+    /// Grid positions coinciding with a corner are skipped, such that each corner is
+    /// emitted exactly once.
+    pub fn with_corners(self) -> CorneredGrid {
+        CorneredGrid::new(self)
+    }
+
+    /// Wraps the iterator such that the next position can be peeked at while
+    /// the exact number of remaining positions is still available.
+    pub fn peekable_counted(self) -> PeekableGrid {
+        PeekableGrid::new(self)
+    }
+
+    /// Pairs each emitted position with its four neighbors on the lattice, in order
+    /// up, down, left and right along the rotated axes (see [`GridPositionIterator::lattice_vectors`]).
+    ///
+    /// Neighbors outside the rectangle (or the clip region) are `None`.
+    pub fn with_neighbors(self) -> impl Iterator<Item = (GridCoord, [Option<GridCoord>; 4])> {
+        let (u, v) = self.lattice_vectors();
+        let min = self.origin;
+        let max = self.origin + Vector::new(self.width, self.height);
+        let epsilon = 1e-9 * self.width.max(self.height);
+        let clip = self.clip.clone();
+
+        let contains = move |point: Vector| {
+            point.x >= min.x - epsilon
+                && point.x <= max.x + epsilon
+                && point.y >= min.y - epsilon
+                && point.y <= max.y + epsilon
+                && clip.as_ref().map_or(true, |clip| clip.contains(point))
+        };
+
+        self.map(move |coord| {
+            let point = Vector::new(coord.x, coord.y);
+            let neighbors = [-v, v, -u, u].map(|offset| {
+                let neighbor = point + offset;
+                contains(neighbor).then(|| GridCoord::new(neighbor.x, neighbor.y))
+            });
+            (coord, neighbors)
+        })
+    }
+
+    /// Restricts the grid to the positions at which an intensity map reaches a threshold,
+    /// e.g. for bitmap-driven halftoning.
+    ///
+    /// The intensity is sampled at each position in the original space; positions whose
+    /// intensity is below the threshold are skipped.
+    pub fn masked<F>(
+        self,
+        intensity: F,
+        threshold: f64,
+    ) -> FilteredGrid<impl Fn(&GridCoord) -> bool>
+    where
+        F: Fn(Vector) -> f64,
+    {
+        self.filtered(move |coord| intensity(Vector::new(coord.x, coord.y)) >= threshold)
+    }
+
+    /// Attaches a dot radius to each emitted position for amplitude-modulated (AM) screening,
+    /// where the size of the dots follows the local tone.
+    ///
+    /// The tone is sampled at each position in the original space and clamped to `0..=1`,
+    /// where `0` produces no dot (a radius of zero) and `1` produces the largest dot not
+    /// overlapping its neighbors, see [`GridPositionIterator::max_nonoverlap_radius`].
+    /// The area of the dot is proportional to the tone, i.e. the radius grows with its
+    /// square root.
+    pub fn am_dots<F>(self, tone: F) -> impl Iterator<Item = (GridCoord, f64)>
+    where
+        F: Fn(Vector) -> f64,
+    {
+        let max_radius = self.max_nonoverlap_radius();
+        self.map(move |coord| {
+            let tone = tone(Vector::new(coord.x, coord.y)).clamp(0.0, 1.0);
+            (coord, max_radius * tone.sqrt())
+        })
+    }
+
+    /// Clamps lattice nodes just outside the rectangle onto its nearest edge instead of
+    /// dropping them, yielding a denser boundary.
+    ///
+    /// Nodes outside the rectangle by no more than half the smaller grid spacing, i.e. nodes
+    /// whose dots of [`GridPositionIterator::max_nonoverlap_radius`] would still overlap the
+    /// rectangle, are projected onto the nearest point of the rectangle. This changes the
+    /// number of positions: it is at least the number of regular positions, and projected
+    /// positions may lie closer to each other than the grid spacing.
+    ///
+    /// The iteration restarts from the beginning and retains all settings of the grid.
+    /// Clip regions and strides are applied to the lattice nodes before they are clamped.
+    /// With [`Boundary::Exclusive`], positions on the right and bottom edges are skipped,
+    /// such that adjacent rectangles do not share positions; if the grid wraps around
+    /// horizontally, nodes beyond the left and right edges are skipped instead of clamped,
+    /// since they coincide with the nodes on the opposite side.
+    pub fn clamp_to_rect(self) -> impl Iterator<Item = GridCoord> {
+        let margin = self.max_nonoverlap_radius();
+        let (min, max) = (
+            self.origin,
+            self.origin + Vector::new(self.width, self.height),
+        );
+
+        // Enlarging the rectangle around the same center retains the lattice.
+        let mut grid = Self::new_at(
+            min - Vector::new(margin, margin),
+            self.width + 2.0 * margin,
+            self.height + 2.0 * margin,
+            self.dx,
+            self.dy,
+            self.x0,
+            self.y0,
+            self.alpha,
+        );
+        grid.copy_settings(&self);
+
+        let exclusive = self.inner.boundary() == Boundary::Exclusive;
+        let wrap_x = self.wrap_x;
+        grid.filter_map(move |coord| {
+            // Nodes beyond the seam coincide with the wrapped nodes on the opposite side.
+            if wrap_x && (coord.x < min.x || coord.x >= max.x) {
+                return None;
+            }
+
+            let clamped = GridCoord::new(coord.x.clamp(min.x, max.x), coord.y.clamp(min.y, max.y));
+            let (dx, dy) = (coord.x - clamped.x, coord.y - clamped.y);
+            if dx * dx + dy * dy > margin * margin {
+                return None;
+            }
+
+            if exclusive && (clamped.x >= max.x || clamped.y >= max.y) {
+                return None;
+            }
+
+            Some(clamped)
+        })
+    }
+
+    /// Transforms each emitted position using the specified closure.
+    ///
+    /// In contrast to [`Iterator::map`], the returned [`MappedGrid`] is a named type that
+    /// still provides the exact number of its positions via [`MappedGrid::exact_count`].
+    pub fn map_coord<F>(self, f: F) -> MappedGrid<F>
+    where
+        F: FnMut(GridCoord) -> GridCoord,
+    {
+        MappedGrid::new(self, f)
+    }
+
+    /// Restricts the grid to the positions matching the specified predicate.
+    ///
+    /// In contrast to [`Iterator::filter`], the returned [`FilteredGrid`] still provides
+    /// an upper bound of its number of positions via [`FilteredGrid::max_count`].
+    pub fn filtered<F>(self, predicate: F) -> FilteredGrid<F>
+    where
+        F: Fn(&GridCoord) -> bool,
+    {
+        FilteredGrid::new(self, predicate)
+    }
+
+    /// Determines the positions present in only one of two grids, e.g. for registration
+    /// diagnostics. Positions closer than the tolerance are considered identical.
+    ///
+    /// The positions of this grid that are missing from the other grid are returned first,
+    /// followed by the positions of the other grid that are missing from this one.
+    /// Both grids are collected into spatial hashes, such that this takes O(n) time.
+    ///
+    /// ## Panics
+    /// Panics if the tolerance is not positive.
+    pub fn symmetric_difference(self, other: Self, tolerance: f64) -> Vec<GridCoord> {
+        let ours: Vec<_> = self.collect();
+        let theirs: Vec<_> = other.collect();
+
+        let mut our_hash = SpatialHash::new(tolerance);
+        for coord in &ours {
+            our_hash.insert(coord.clone());
+        }
+
+        let mut their_hash = SpatialHash::new(tolerance);
+        for coord in &theirs {
+            their_hash.insert(coord.clone());
+        }
+
+        let only_ours = ours
+            .into_iter()
+            .filter(|coord| !their_hash.contains_near(coord));
+        let only_theirs = theirs
+            .into_iter()
+            .filter(|coord| !our_hash.contains_near(coord));
+        only_ours.chain(only_theirs).collect()
+    }
+
+    /// Reports the intersection tests of the row at the specified Y coordinate (in rotated
+    /// grid space) with the edges of the rectangle, e.g. to diagnose positions missing
+    /// from rows at shallow angles. See [`RowDebug`] for details.
+    pub fn debug_row(&self, y: f64) -> RowDebug {
+        self.inner.debug_row(y)
+    }
+
+    /// Returns an iterator over the grid positions that lie on the boundary of the grid,
+    /// i.e. the first and last position of each row as well as all positions of the
+    /// first and last row.
+    pub fn boundary(mut self) -> impl Iterator<Item = GridCoord> {
+        let mut rows = std::iter::from_fn(move || self.next_row()).peekable();
+        let mut is_first = true;
+        std::iter::from_fn(move || {
+            let mut row = rows.next()?;
+            let is_last = rows.peek().is_none();
+            if !is_first && !is_last && row.len() > 2 {
+                let last = row.pop().expect("row is not empty");
+                row.truncate(1);
+                row.push(last);
+            }
+            is_first = false;
+            Some(row)
+        })
+        .flatten()
+    }
+
+    /// Advances the iterator by an entire row, returning all positions of that row.
+    /// Rows without any (non-clipped) positions are skipped.
+    fn next_row(&mut self) -> Option<Vec<GridCoord>> {
+        loop {
+            let (y, xs) = self.inner.next_row()?;
+            let row: Vec<_> = xs.filter_map(|x| self.emit(Vector::new(x, y))).collect();
+            if !row.is_empty() {
+                return Some(row);
+            }
+        }
+    }
+
+    /// Un-rotates a point from rotated grid space into the original space.
+    fn unrotate(&self, point: Vector) -> GridCoord {
+        let x = point.x;
+        let y = point.y;
+        let center = self.inner.center();
+
+        let unrotated_x = (x - center.x) * self.inv_cos - (y - center.y) * self.inv_sin + center.x;
+        let unrotated_y = (x - center.x) * self.inv_sin + (y - center.y) * self.inv_cos + center.y;
+
+        GridCoord::new(unrotated_x, unrotated_y)
+    }
+
+    /// Converts a point from the original space into continuous lattice coordinates,
+    /// i.e. the (fractional) number of grid cells along the rotated X and Y axes,
+    /// such that integer coordinates correspond to grid nodes.
+    fn to_lattice(&self, point: Vector) -> Vector {
+        let center = self.inner.center();
+        let rotated = self.to_grid_space(point);
+        Vector::new(
+            (rotated.x - center.x - self.x0) / self.dx,
+            (rotated.y - center.y - self.y0) / self.dy,
+        )
+    }
+
+    /// Converts continuous lattice coordinates back into a point in the original space.
+    fn lattice_point(&self, lattice: Vector) -> GridCoord {
+        let center = self.inner.center();
+        self.unrotate(Vector::new(
+            center.x + self.x0 + lattice.x * self.dx,
+            center.y + self.y0 + lattice.y * self.dy,
+        ))
+    }
+
+    /// Creates a new iterator for the same grid with different offsets, starting from the beginning.
+    fn with_offset(&self, x0: f64, y0: f64) -> Self {
+        let mut grid = Self::new_at(
+            self.origin,
+            self.width,
+            self.height,
+            self.dx,
+            self.dy,
+            x0,
+            y0,
+            self.alpha,
+        );
+        grid.copy_settings(self);
+        grid
+    }
+
+    /// Applies the settings of another grid, i.e. its clip region, stride, boundary handling,
+    /// starting corner, serpentine order and horizontal wrapping, to this grid.
+    fn copy_settings(&mut self, other: &Self) {
+        self.clip = other.clip.clone();
+        self.stride = other.stride;
+        self.inner.set_boundary(other.inner.boundary());
+        self.inner.set_start_corner(other.inner.start_corner());
+        self.inner.set_serpentine(other.inner.serpentine());
+        self.wrap_x = other.wrap_x;
+    }
+
+    /// Determines whether any positions of the grid may be skipped.
+    fn is_filtered(&self) -> bool {
+        self.clip.is_some() || self.stride != (1, 1) || self.wrap_x
+    }
+
+    /// Un-rotates a point from rotated grid space into the original space
+    /// unless it is skipped due to the stride or clip region.
+    fn emit(&self, point: Vector) -> Option<GridCoord> {
+        let (col_stride, row_stride) = self.stride;
+        if self.stride != (1, 1) {
+            let center = self.inner.center();
+            let column = ((point.x - center.x - self.x0) / self.dx).round() as i64;
+            let row = ((point.y - center.y - self.y0) / self.dy).round() as i64;
+            if column.rem_euclid(col_stride as i64) != 0 || row.rem_euclid(row_stride as i64) != 0 {
+                return None;
+            }
+        }
+
+        let coord = self.unrotate(point);
+        if self.wrap_x {
+            // Nodes on the right edge duplicate the nodes on the left edge.
+            let seam = self.origin.x + self.width - 1e-9 * self.width.max(1.0);
+            if coord.x >= seam {
+                return None;
+            }
+        }
+
+        match &self.clip {
+            Some(clip) if !clip.contains(Vector::new(coord.x, coord.y)) => None,
+            _ => Some(coord),
+        }
+    }
+
+    /// Provides an estimated upper bound for the number of grid points.
+    /// This is only correct for unrotated grids; rotated grids produce smaller values.
+    fn estimate_max_grid_points(&self) -> usize {
+        let num_points_x = (self.width + self.dx) / self.dx;
+        let num_points_y = (self.height + self.dy) / self.dy;
+        (num_points_x * num_points_y).ceil() as _
+    }
+}
+
+impl Iterator for GridPositionIterator {
+    type Item = GridCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let point = self.inner.next()?;
+
+            // Un-rotate the point unless it is skipped.
+            if let Some(coord) = self.emit(point) {
+                return Some(coord);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.estimate_max_grid_points()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::grid;
+
+    #[test]
+    fn test() {
+        const WIDTH: f64 = 10240.0;
+        const HEIGHT: f64 = 128.0;
+        const ANGLE: f64 = 45.0;
+
+        for _ in 0..1000 {
+            let grid = GridPositionIterator::new(
+                WIDTH as _,
+                HEIGHT as _,
+                7.0,
+                7.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(ANGLE),
+            );
+
+            let mut count = 0;
+            for _ in grid.into_iter() {
+                count += 1;
+            }
+
+            assert!(count > 0);
+        }
+    }
+
+    #[test]
+    fn test_cropped_is_phase_aligned_subset() {
+        let min = Vector::new(20.0, 10.0);
+        let max = Vector::new(60.0, 45.0);
+
+        let full: Vec<_> = GridPositionIterator::new(
+            100.0,
+            80.0,
+            7.0,
+            7.0,
+            1.0,
+            2.0,
+            Angle::<f64>::from_degrees(15.0),
+        )
+        .collect();
+
+        let cropped: Vec<_> = GridPositionIterator::new_cropped(
+            (100.0, 80.0),
+            (min, max),
+            7.0,
+            7.0,
+            1.0,
+            2.0,
+            Angle::<f64>::from_degrees(15.0),
+        )
+        .collect();
+
+        assert!(!cropped.is_empty());
+        for coord in &cropped {
+            assert!(coord.x >= min.x && coord.x <= max.x);
+            assert!(coord.y >= min.y && coord.y <= max.y);
+            assert!(full.iter().any(|c| c.approx_eq(coord, 1e-9)));
+        }
+
+        let expected = full
+            .iter()
+            .filter(|c| c.x >= min.x && c.x <= max.x && c.y >= min.y && c.y <= max.y)
+            .count();
+        assert_eq!(cropped.len(), expected);
+    }
+
+    #[test]
+    fn test_boundary_is_outer_ring() {
+        let grid = || {
+            GridPositionIterator::new(
+                50.0,
+                40.0,
+                7.0,
+                7.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(0.0),
+            )
+        };
+
+        let full: Vec<_> = grid().collect();
+        let min_x = full.iter().map(|c| c.x).fold(f64::INFINITY, f64::min);
+        let max_x = full.iter().map(|c| c.x).fold(f64::NEG_INFINITY, f64::max);
+        let min_y = full.iter().map(|c| c.y).fold(f64::INFINITY, f64::min);
+        let max_y = full.iter().map(|c| c.y).fold(f64::NEG_INFINITY, f64::max);
+
+        let ring: Vec<_> = full
+            .iter()
+            .filter(|c| c.x == min_x || c.x == max_x || c.y == min_y || c.y == max_y)
+            .cloned()
+            .collect();
+
+        let boundary: Vec<_> = grid().boundary().collect();
+        assert_eq!(boundary, ring);
+        assert!(boundary.len() < full.len());
+    }
+
+    #[test]
+    fn test_exact_count() {
+        let grid = GridPositionIterator::new(
+            100.0,
+            80.0,
+            7.0,
+            5.0,
+            1.0,
+            2.0,
+            Angle::<f64>::from_degrees(15.0),
+        );
+        assert_eq!(grid.exact_count(), grid.count());
+
+        let mut grid = GridPositionIterator::new_cropped(
+            (100.0, 80.0),
+            (Vector::new(20.0, 10.0), Vector::new(60.0, 45.0)),
+            7.0,
+            5.0,
+            1.0,
+            2.0,
+            Angle::<f64>::from_degrees(15.0),
+        );
+        grid.next();
+        assert_eq!(grid.exact_count(), grid.count());
+    }
+
+    #[test]
+    fn test_try_new() {
+        let angle = Angle::<f64>::from_degrees(15.0);
+        assert!(GridPositionIterator::try_new(10.0, 10.0, 1.0, 1.0, 0.0, 0.0, angle).is_ok());
+        assert_eq!(
+            GridPositionIterator::try_new(0.0, 10.0, 1.0, 1.0, 0.0, 0.0, angle).err(),
+            Some(GridError::NonPositiveDimension)
+        );
+        assert_eq!(
+            GridPositionIterator::try_new(10.0, 10.0, 1.0, -1.0, 0.0, 0.0, angle).err(),
+            Some(GridError::NonPositiveSpacing)
+        );
+        assert_eq!(
+            GridPositionIterator::try_new(
+                10.0,
+                10.0,
+                1.0,
+                1.0,
+                0.0,
+                0.0,
+                Angle::from_radians(f64::NAN)
+            )
+            .err(),
+            Some(GridError::NonFiniteAngle)
+        );
+        assert_eq!(
+            GridPositionIterator::try_new(
+                10.0,
+                10.0,
+                1.0,
+                1.0,
+                0.0,
+                0.0,
+                Angle::from_degrees(120.0)
+            )
+            .err(),
+            Some(GridError::AngleOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_center_anchored() {
+        for angle in [0.0, 15.0, 45.0, 75.0, 90.0] {
+            let grid = GridPositionIterator::new(
+                101.0,
+                37.0,
+                7.0,
+                5.0,
+                3.0,
+                1.5,
+                Angle::<f64>::from_degrees(angle),
+            )
+            .with_center_anchored(true);
+
+            let center = Vector::new(50.5, 18.5);
+            let center_coord = GridCoord::new(center.x, center.y);
+            assert_coord_eq!(grid.nearest(center), center_coord);
+            assert!(grid.into_iter().any(|c| c.approx_eq(&center_coord, 1e-9)));
+        }
+    }
+
+    #[test]
+    fn test_seamless() {
+        let (width, height) = (60.0, 40.0);
+        let points: Vec<_> =
+            GridPositionIterator::seamless(width, height, 6, 4, Angle::<f64>::default()).collect();
+        assert_eq!(points.len(), 24);
+
+        let xs = |y: f64| -> Vec<f64> {
+            let mut xs: Vec<_> = points
+                .iter()
+                .filter(|p| (p.y - y).abs() < 1e-9)
+                .map(|p| p.x)
+                .collect();
+            xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            xs
+        };
+        let ys = |x: f64| -> Vec<f64> {
+            let mut ys: Vec<_> = points
+                .iter()
+                .filter(|p| (p.x - x).abs() < 1e-9)
+                .map(|p| p.y)
+                .collect();
+            ys.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            ys
+        };
+
+        // The top and bottom rows, as well as the left and right columns, line up.
+        let (top, bottom) = (xs(5.0), xs(35.0));
+        let (left, right) = (ys(5.0), ys(55.0));
+        assert_eq!(top.len(), 6);
+        assert_eq!(left.len(), 4);
+        for (a, b) in top.iter().zip(&bottom).chain(left.iter().zip(&right)) {
+            assert!((a - b).abs() < 1e-9);
+        }
+
+        // The spacing continues across the seams.
+        assert!((top[0] + width - top[5] - 10.0).abs() < 1e-9);
+        assert!((left[0] + height - left[3] - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "not commensurate")]
+    fn test_seamless_incommensurate_angle() {
+        GridPositionIterator::seamless(60.0, 40.0, 6, 4, Angle::<f64>::from_degrees(15.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "the spacing must be positive")]
+    fn test_negative_spacing() {
+        GridPositionIterator::new(60.0, 40.0, -7.0, 7.0, 0.0, 0.0, Angle::<f64>::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "the spacing must be positive and finite")]
+    fn test_infinite_spacing() {
+        GridPositionIterator::new(
+            60.0,
+            40.0,
+            f64::INFINITY,
+            7.0,
+            0.0,
+            0.0,
+            Angle::<f64>::default(),
+        );
+    }
+
+    #[test]
+    fn test_try_new_negative_spacing() {
+        let grid =
+            GridPositionIterator::try_new(60.0, 40.0, -7.0, 7.0, 0.0, 0.0, Angle::<f64>::default());
+        assert_eq!(grid.err(), Some(GridError::NonPositiveSpacing));
+    }
+
+    #[test]
+    fn test_try_new_non_finite() {
+        let angle = Angle::<f64>::default();
+        for spacing in [f64::INFINITY, f64::NAN] {
+            let grid = GridPositionIterator::try_new(60.0, 40.0, spacing, 7.0, 0.0, 0.0, angle);
+            assert_eq!(grid.err(), Some(GridError::NonPositiveSpacing));
+            let grid = GridPositionIterator::try_new(60.0, 40.0, 7.0, spacing, 0.0, 0.0, angle);
+            assert_eq!(grid.err(), Some(GridError::NonPositiveSpacing));
+        }
+
+        for dimension in [f64::INFINITY, f64::NAN] {
+            let grid = GridPositionIterator::try_new(dimension, 40.0, 7.0, 7.0, 0.0, 0.0, angle);
+            assert_eq!(grid.err(), Some(GridError::NonPositiveDimension));
+            let grid = GridPositionIterator::try_new(60.0, dimension, 7.0, 7.0, 0.0, 0.0, angle);
+            assert_eq!(grid.err(), Some(GridError::NonPositiveDimension));
+        }
+    }
+
+    #[test]
+    fn test_new_v() {
+        let angle = Angle::<f64>::from_degrees(15.0);
+        let vectors: Vec<_> = GridPositionIterator::new_v(
+            Vector::new(60.0, 40.0),
+            Vector::new(7.0, 5.0),
+            Vector::new(1.0, 2.0),
+            angle,
+        )
+        .collect();
+        let scalars: Vec<_> =
+            GridPositionIterator::new(60.0, 40.0, 7.0, 5.0, 1.0, 2.0, angle).collect();
+        assert_eq!(vectors, scalars);
+    }
+
+    #[test]
+    fn test_new_at() {
+        let angle = Angle::<f64>::from_degrees(15.0);
+        let shifted: Vec<_> = GridPositionIterator::new_at(
+            Vector::new(100.0, 100.0),
+            60.0,
+            40.0,
+            7.0,
+            5.0,
+            1.0,
+            2.0,
+            angle,
+        )
+        .collect();
+        let expected: Vec<_> = GridPositionIterator::new(60.0, 40.0, 7.0, 5.0, 1.0, 2.0, angle)
+            .map(|c| GridCoord::new(c.x + 100.0, c.y + 100.0))
+            .collect();
+
+        assert_eq!(shifted.len(), expected.len());
+        for (a, b) in shifted.iter().zip(&expected) {
+            assert_coord_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn test_in_concave_polygon() {
+        let vertices = [
+            Vector::new(10.0, 10.0),
+            Vector::new(50.0, 10.0),
+            Vector::new(50.0, 30.0),
+            Vector::new(30.0, 30.0),
+            Vector::new(30.0, 50.0),
+            Vector::new(10.0, 50.0),
+        ];
+        let angle = Angle::<f64>::from_degrees(15.0);
+
+        let clipped: Vec<_> =
+            GridPositionIterator::new_in_polygon_general(&vertices, 3.0, 3.0, 0.0, 0.0, angle)
+                .collect();
+        let full: Vec<_> = GridPositionIterator::new_at(
+            Vector::new(10.0, 10.0),
+            40.0,
+            40.0,
+            3.0,
+            3.0,
+            0.0,
+            0.0,
+            angle,
+        )
+        .collect();
+
+        let in_notch = |c: &GridCoord| c.x > 30.0 && c.y > 30.0;
+        assert!(full.iter().any(in_notch));
+        assert!(!clipped.iter().any(in_notch));
+
+        let expected: Vec<_> = full.into_iter().filter(|c| !in_notch(c)).collect();
+        assert_eq!(clipped, expected);
+    }
+
+    #[test]
+    fn test_narrower_than_spacing() {
+        // The rectangle's center is at x = 1.5.
+        for (x0, expected_columns) in [
+            (0.0, 1),
+            (1.5, 1),
+            (-1.5, 1),
+            (2.0, 0),
+            (5.0, 0),
+            (-5.0, 0),
+            (9.0, 1),
+            (-9.0, 1),
+        ] {
+            let coords: Vec<_> = GridPositionIterator::new(
+                3.0,
+                30.0,
+                10.0,
+                10.0,
+                x0,
+                0.0,
+                Angle::<f64>::from_degrees(0.0),
+            )
+            .collect();
+
+            let mut columns: Vec<_> = coords.iter().map(|c| c.x).collect();
+            columns.dedup();
+            assert_eq!(columns.len(), expected_columns, "x0 = {x0}");
+            assert!(coords.iter().all(|c| c.x >= 0.0 && c.x <= 3.0));
+            if expected_columns == 1 {
+                assert_eq!(coords.len(), 3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_row_through_corners() {
+        // At 45°, the center row of the square runs exactly through its left and right corners,
+        // where rounding places the intersections just outside of the edges.
+        let coords: Vec<_> = GridPositionIterator::new(
+            10.0,
+            10.0,
+            3.0,
+            9.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(45.0),
+        )
+        .collect();
+
+        assert_eq!(coords.len(), 5);
+        assert!(coords
+            .iter()
+            .any(|c| c.approx_eq(&GridCoord::new(5.0, 5.0), 1e-9)));
+        assert!(coords.iter().all(|c| (c.x + c.y - 10.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_smaller_than_unit() {
+        // Edges shorter than one unit must not reject rows crossing them.
+        let coords: Vec<_> = GridPositionIterator::new(
+            0.5,
+            0.3,
+            7.0,
+            7.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(30.0),
+        )
+        .collect();
+
+        assert_eq!(coords.len(), 1);
+        assert!(coords[0].approx_eq(&GridCoord::new(0.25, 0.15), 1e-9));
+    }
+
+    #[test]
+    fn test_with_stride() {
+        let grid = || {
+            GridPositionIterator::new(
+                55.0,
+                55.0,
+                7.0,
+                7.0,
+                3.5,
+                3.5,
+                Angle::<f64>::from_degrees(0.0),
+            )
+        };
+
+        let full: Vec<_> = grid().collect();
+        assert_eq!(full.len(), 64);
+
+        let strided = grid().with_stride(2, 2);
+        assert_eq!(strided.exact_count(), 16);
+
+        let strided: Vec<_> = strided.collect();
+        assert_eq!(strided.len(), 16);
+        for coord in &strided {
+            assert!(full.contains(coord));
+        }
+
+        let columns = |coords: &[GridCoord]| {
+            let mut xs: Vec<_> = coords.iter().map(|c| c.x).collect();
+            xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            xs.dedup();
+            xs
+        };
+        let all_columns = columns(&full);
+        let strided_columns = columns(&strided);
+        assert_eq!(strided_columns.len(), 4);
+        assert!(strided_columns
+            .windows(2)
+            .all(|w| (w[1] - w[0] - 14.0).abs() < 1e-9));
+        assert!(all_columns.contains(&strided_columns[0]));
+    }
+
+    #[test]
+    fn test_dither_at() {
+        let grid = GridPositionIterator::new(
+            100.0,
+            100.0,
+            10.0,
+            10.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(15.0),
+        );
+
+        let dot = grid.lattice_point(Vector::new(2.0, -1.0));
+        let dot = Vector::new(dot.x, dot.y);
+        assert_eq!(grid.dither_at(dot, 1.0), 1.0);
+
+        // The center of a grid cell is furthest away from any node.
+        let cell_center = grid.lattice_point(Vector::new(0.5, 0.5));
+        let cell_center = Vector::new(cell_center.x, cell_center.y);
+        assert_eq!(grid.dither_at(cell_center, 1.0), 0.0);
+
+        let partial = grid.dither_at(dot + Vector::new(1.25, 0.0), 1.0);
+        assert!(partial > 0.0 && partial < 1.0);
+    }
+
+    #[test]
+    fn test_exclusive_boundary_at_tile_seam() {
+        let tile = |x: f64, boundary: Boundary| {
+            GridPositionIterator::new_at(
+                Vector::new(x, 0.0),
+                40.0,
+                40.0,
+                10.0,
+                10.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(0.0),
+            )
+            .with_boundary(boundary)
+        };
+
+        let left: Vec<_> = tile(0.0, Boundary::Inclusive).collect();
+        let right: Vec<_> = tile(40.0, Boundary::Inclusive).collect();
+        assert_eq!(left.len(), 25);
+        assert!(left.iter().any(|coord| right.contains(coord)));
+
+        let left: Vec<_> = tile(0.0, Boundary::Exclusive).collect();
+        let right: Vec<_> = tile(40.0, Boundary::Exclusive).collect();
+        assert_eq!(left.len(), 16);
+        assert_eq!(right.len(), 16);
+        assert!(left.iter().all(|coord| !right.contains(coord)));
+        assert!(left.iter().all(|coord| coord.x < 40.0 && coord.y < 40.0));
+    }
+
+    #[test]
+    fn test_pixel_aligned() {
+        let is_pixel_center = |value: f64| (value - 0.5 - (value - 0.5).round()).abs() < 1e-9;
+
+        let grid = GridPositionIterator::new_pixel_aligned(
+            101.0,
+            64.0,
+            7.3,
+            6.8,
+            Angle::<f64>::from_degrees(0.0),
+        );
+        let (u, v) = grid.lattice_vectors();
+        assert_vec_eq!(u, Vector::new(7.0, 0.0));
+        assert_vec_eq!(v, Vector::new(0.0, 7.0));
+
+        let coords: Vec<_> = grid.collect();
+        assert!(!coords.is_empty());
+        for coord in coords {
+            assert!(is_pixel_center(coord.x), "x = {}", coord.x);
+            assert!(is_pixel_center(coord.y), "y = {}", coord.y);
+        }
+
+        let grid = GridPositionIterator::new_pixel_aligned(
+            100.0,
+            100.0,
+            8.0,
+            8.0,
+            Angle::<f64>::from_degrees(15.0),
+        );
+        let coords: Vec<_> = grid.collect();
+        assert!(!coords.is_empty());
+        for coord in coords {
+            assert!(is_pixel_center(coord.x), "x = {}", coord.x);
+            assert!(is_pixel_center(coord.y), "y = {}", coord.y);
+        }
+    }
+
+    #[test]
+    fn test_count_in_rect_partition() {
+        for degrees in [0.0, 15.0, 45.0, 72.5] {
+            let grid = || {
+                GridPositionIterator::new(
+                    100.0,
+                    80.0,
+                    6.0,
+                    5.0,
+                    0.0,
+                    0.0,
+                    Angle::<f64>::from_degrees(degrees),
+                )
+            };
+            let coords: Vec<_> = grid().collect();
+            let grid = grid();
+
+            // The outer tiles extend beyond the rectangle to include positions on its edges.
+            let xs = [-1.0, 30.0, 50.0, 77.7, 101.0];
+            let ys = [-1.0, 20.0, 45.5, 81.0];
+            let mut total = 0;
+            for x in xs.windows(2) {
+                for y in ys.windows(2) {
+                    let (min, max) = (Vector::new(x[0], y[0]), Vector::new(x[1], y[1]));
+                    let count = grid.count_in_rect(min, max);
+                    let expected = coords
+                        .iter()
+                        .filter(|c| min.x <= c.x && c.x < max.x && min.y <= c.y && c.y < max.y)
+                        .count();
+                    assert_eq!(count, expected, "{degrees}° in {min:?}..{max:?}");
+                    total += count;
+                }
+            }
+
+            assert_eq!(total, coords.len());
+        }
+    }
+
+    #[test]
+    fn test_progress() {
+        let mut grid = GridPositionIterator::new(
+            80.0,
+            60.0,
+            5.0,
+            5.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(30.0),
+        );
+        assert_eq!(grid.progress(), 0.0);
+
+        let total = grid.exact_count();
+        let mut previous = 0.0;
+        for _ in 0..total - 1 {
+            grid.next().expect("positions remain");
+            let progress = grid.progress();
+            assert!(progress >= previous);
+            previous = progress;
+        }
+        assert!(previous > 0.9);
+
+        assert!(grid.next().is_some());
+        assert!(grid.next().is_none());
+        assert_eq!(grid.progress(), 1.0);
+    }
+
+    #[test]
+    fn test_sample_approx() {
+        for (degrees, target) in [(0.0, 1000), (15.0, 1000), (45.0, 250), (75.0, 5000)] {
+            let grid = GridPositionIterator::new(
+                2000.0,
+                1500.0,
+                4.0,
+                4.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(degrees),
+            );
+            let count = grid.sample_approx(target).count() as f64;
+            let error = (count - target as f64).abs() / target as f64;
+            assert!(error < 0.1, "{count} positions for a target of {target}");
+        }
+
+        let grid = || {
+            GridPositionIterator::new(
+                50.0,
+                50.0,
+                10.0,
+                10.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(0.0),
+            )
+        };
+        assert_eq!(grid().sample_approx(1000).count(), grid().count());
+    }
+
+    #[test]
+    fn test_from_lpi() {
+        let grid = GridPositionIterator::from_lpi(
+            20.0,
+            10.0,
+            150.0,
+            300.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(0.0),
+        );
+
+        let (u, v) = grid.lattice_vectors();
+        assert_vec_eq!(u, Vector::new(2.0, 0.0));
+        assert_vec_eq!(v, Vector::new(0.0, 2.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "the screen frequency and resolution must be positive")]
+    fn test_from_lpi_zero_frequency() {
+        GridPositionIterator::from_lpi(20.0, 10.0, 0.0, 300.0, 0.0, 0.0, Angle::<f64>::default());
+    }
+
+    #[test]
+    fn test_with_neighbors() {
+        let grid = GridPositionIterator::new(
+            40.0,
+            40.0,
+            10.0,
+            10.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(0.0),
+        );
+
+        let points: Vec<_> = grid.with_neighbors().collect();
+        assert_eq!(points.len(), 25);
+
+        let neighbors_of = |x: f64, y: f64| {
+            let (_, neighbors) = points
+                .iter()
+                .find(|(coord, _)| coord.approx_eq(&GridCoord::new(x, y), 1e-9))
+                .expect("point exists");
+            neighbors.clone()
+        };
+
+        let [up, down, left, right] = neighbors_of(20.0, 20.0);
+        assert_coord_eq!(up.expect("up"), GridCoord::new(20.0, 10.0));
+        assert_coord_eq!(down.expect("down"), GridCoord::new(20.0, 30.0));
+        assert_coord_eq!(left.expect("left"), GridCoord::new(10.0, 20.0));
+        assert_coord_eq!(right.expect("right"), GridCoord::new(30.0, 20.0));
+
+        let corner = neighbors_of(0.0, 0.0);
+        assert_eq!(corner.iter().flatten().count(), 2);
+        assert!(corner[0].is_none() && corner[2].is_none());
+    }
+
+    #[test]
+    fn test_symmetric_difference() {
+        let grid = |x0: f64| {
+            GridPositionIterator::new(
+                60.0,
+                40.0,
+                6.0,
+                6.0,
+                x0,
+                0.0,
+                Angle::<f64>::from_degrees(15.0),
+            )
+        };
+
+        assert!(grid(0.0).symmetric_difference(grid(0.0), 1e-6).is_empty());
+
+        let difference = grid(0.0).symmetric_difference(grid(3.0), 1e-6);
+        assert_eq!(difference.len(), grid(0.0).count() + grid(3.0).count());
+    }
+
+    #[test]
+    fn test_start_corner() {
+        let grid = |degrees: f64, corner: Corner| {
+            GridPositionIterator::new(
+                50.0,
+                30.0,
+                10.0,
+                10.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(degrees),
+            )
+            .with_start_corner(corner)
+        };
+
+        let corners = [
+            Corner::TopLeft,
+            Corner::TopRight,
+            Corner::BottomLeft,
+            Corner::BottomRight,
+        ];
+
+        for degrees in [0.0, 30.0] {
+            let sorted = |corner: Corner| {
+                let mut coords: Vec<_> = grid(degrees, corner).collect();
+                coords.sort_by(|a, b| (a.y, a.x).partial_cmp(&(b.y, b.x)).unwrap());
+                coords
+            };
+
+            let expected = sorted(Corner::TopLeft);
+            for corner in corners {
+                let actual = sorted(corner);
+                assert_eq!(actual.len(), expected.len());
+                for (a, b) in actual.iter().zip(&expected) {
+                    assert_coord_eq!(a, b);
+                }
+                assert_eq!(grid(degrees, corner).exact_count(), expected.len());
+            }
+        }
+
+        // At 0°, the rotated grid space coincides with the original space.
+        let first = |corner: Corner| grid(0.0, corner).next().expect("grid is not empty");
+        assert_coord_eq!(first(Corner::TopLeft), GridCoord::new(5.0, 5.0));
+        assert_coord_eq!(first(Corner::TopRight), GridCoord::new(45.0, 5.0));
+        assert_coord_eq!(first(Corner::BottomLeft), GridCoord::new(5.0, 25.0));
+        assert_coord_eq!(first(Corner::BottomRight), GridCoord::new(45.0, 25.0));
+    }
+
+    #[test]
+    fn test_row_count() {
+        for (height, boundary) in [
+            (30.0, Boundary::Inclusive),
+            (30.0, Boundary::Exclusive),
+            (34.0, Boundary::Inclusive),
+        ] {
+            let grid = GridPositionIterator::new(
+                50.0,
+                height,
+                5.0,
+                5.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(0.0),
+            )
+            .with_boundary(boundary);
+            let row_count = grid.row_count();
+
+            let mut ys: Vec<_> = grid.map(|coord| coord.y).collect();
+            ys.dedup();
+            assert_eq!(row_count, ys.len(), "{height} {boundary:?}");
+        }
+
+        let mut grid = GridPositionIterator::new(
+            80.0,
+            60.0,
+            5.0,
+            5.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(30.0),
+        );
+        let row_count = grid.row_count();
+        let mut rows = 0;
+        while grid.inner.next_row().is_some() {
+            rows += 1;
+        }
+        assert_eq!(row_count, rows);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut grid = grid(6.0, 25.0)
+            .with_stride(2, 1)
+            .with_start_corner(Corner::BottomRight);
+
+        let first: Vec<_> = grid.by_ref().collect();
+        assert!(!first.is_empty());
+        assert!(grid.next().is_none());
+
+        grid.reset();
+        let second: Vec<_> = grid.by_ref().collect();
+        assert_eq!(first, second);
+
+        // Resetting a partially consumed iterator restarts it as well.
+        grid.reset();
+        grid.next();
+        grid.next();
+        grid.reset();
+        assert_eq!(grid.collect::<Vec<_>>(), first);
+    }
+
+    #[test]
+    fn test_masked() {
+        let count = grid(6.0, 25.0).count();
+
+        assert_eq!(grid(6.0, 25.0).masked(|_| 0.75, 0.5).count(), count);
+        assert_eq!(grid(6.0, 25.0).masked(|_| 0.25, 0.5).count(), 0);
+
+        // A gradient along X keeps only the right half.
+        let right: Vec<_> = grid(6.0, 25.0).masked(|p| p.x / 60.0, 0.5).collect();
+        assert!(!right.is_empty() && right.len() < count);
+        assert!(right.iter().all(|coord| coord.x >= 30.0));
+    }
+
+    #[test]
+    fn test_from_center() {
+        let center = Vector::new(40.0, 25.0);
+        let half_extent = Vector::new(30.0, 20.0);
+        let alpha = Angle::<f64>::from_degrees(20.0);
+
+        let grid =
+            GridPositionIterator::from_center(center, half_extent, 5.0, 5.0, 1.0, 0.0, alpha);
+        let expected = GridPositionIterator::new_at(
+            center - half_extent,
+            2.0 * half_extent.x,
+            2.0 * half_extent.y,
+            5.0,
+            5.0,
+            1.0,
+            0.0,
+            alpha,
+        );
+
+        assert_eq!(grid.collect::<Vec<_>>(), expected.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_max_nonoverlap_radius() {
+        let grid = GridPositionIterator::new(
+            100.0,
+            100.0,
+            10.0,
+            6.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(30.0),
+        );
+        let radius = grid.max_nonoverlap_radius();
+        assert_eq!(radius, 3.0);
+
+        // The overall coverage cannot exceed 100% and matches the area of the dots.
+        let mut coverage = 0.0;
+        for y in 20..80 {
+            for x in 20..80 {
+                coverage += grid.dither_at(Vector::new(x as f64 + 0.5, y as f64 + 0.5), radius);
+            }
+        }
+        let coverage = coverage / (60.0 * 60.0);
+        let expected = std::f64::consts::PI * radius * radius / (10.0 * 6.0);
+        assert!(coverage <= 1.0);
+        assert!((coverage - expected).abs() < 0.05, "coverage is {coverage}");
+
+        // Dots on neighboring nodes along the closer axis touch.
+        let a = grid.lattice_point(Vector::new(0.0, 0.0));
+        let b = grid.lattice_point(Vector::new(0.0, 1.0));
+        let (a, b) = (Vector::new(a.x, a.y), Vector::new(b.x, b.y));
+        for step in 0..=10 {
+            let point = a + (b - a) * (step as f64 / 10.0);
+            assert!(grid.dither_at(point, radius) >= 0.5 - 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_wrap_x_seam_is_continuous() {
+        let grid = |alpha: f64| {
+            GridPositionIterator::new(60.0, 30.0, 10.0, 10.0, 3.0, 0.0, Angle::from_degrees(alpha))
+                .with_wrap_x(true)
+        };
+
+        for alpha in [0.0, 90.0] {
+            let y = grid(alpha).next().unwrap().y;
+            let mut xs: Vec<_> = grid(alpha)
+                .filter(|c| (c.y - y).abs() < 1e-9)
+                .map(|c| c.x)
+                .collect();
+            xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            assert_eq!(xs.len(), 6);
+
+            // Consecutive nodes are evenly spaced, including across the seam.
+            let wrapped = xs[0] + 60.0;
+            for (a, b) in xs.iter().zip(xs.iter().skip(1).chain([&wrapped])) {
+                assert!((b - a - 10.0).abs() < 1e-9, "{a} -> {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_wrap_x_adjusts_spacing_and_skips_seam() {
+        let mut grid = GridPositionIterator::new(
+            50.0,
+            20.0,
+            12.0,
+            10.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(0.0),
+        )
+        .with_wrap_x(true);
+        assert_eq!(grid.lattice_vectors().0, Vector::new(12.5, 0.0));
+
+        let count = grid.exact_count();
+        let coords: Vec<_> = grid.by_ref().collect();
+        assert_eq!(coords.len(), count);
+        assert!(coords.iter().all(|c| c.x < 50.0));
+        assert_eq!(coords.iter().filter(|c| c.y == 10.0).count(), 4);
+    }
+
+    #[test]
+    fn test_serpentine() {
+        let grid = || {
+            GridPositionIterator::new(
+                60.0,
+                45.0,
+                7.0,
+                6.0,
+                0.5,
+                1.0,
+                Angle::<f64>::from_degrees(20.0),
+            )
+        };
+
+        let sorted = |grid: GridPositionIterator| {
+            let mut coords: Vec<_> = grid.map(|c| (c.x, c.y)).collect();
+            coords.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            coords
+        };
+        assert_eq!(sorted(grid().serpentine()), sorted(grid()));
+        assert_eq!(grid().serpentine().exact_count(), grid().exact_count());
+
+        // Consecutive rows scan in opposite directions along the rotated X axis.
+        let (step, _) = grid().lattice_vectors();
+        let mut serpentine = grid().serpentine();
+        let mut rows = 0;
+        let mut index = 0;
+        while let Some(row) = serpentine.next_row() {
+            if let [first, second, ..] = row.as_slice() {
+                let delta = Vector::new(second.x - first.x, second.y - first.y);
+                assert_eq!(delta.dot(&step) > 0.0, index % 2 == 0);
+                rows += 1;
+            }
+            index += 1;
+        }
+        assert!(rows > 3);
+    }
+
+    #[test]
+    fn test_count_in_rect_serpentine() {
+        let grid = || {
+            GridPositionIterator::new(
+                80.0,
+                60.0,
+                5.0,
+                5.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(30.0),
+            )
+        };
+
+        let (min, max) = (Vector::new(10.0, 15.0), Vector::new(55.0, 40.0));
+        assert_eq!(
+            grid().serpentine().count_in_rect(min, max),
+            grid().count_in_rect(min, max)
+        );
+    }
+
+    #[test]
+    fn test_align_to() {
+        let mut grid = GridPositionIterator::new(
+            100.0,
+            80.0,
+            7.0,
+            5.0,
+            1.0,
+            2.0,
+            Angle::<f64>::from_degrees(22.5),
+        );
+
+        for reference in [Vector::new(31.37, 42.11), Vector::new(-3.5, 120.25)] {
+            grid.align_to(reference);
+            let node = grid.nearest(reference);
+            assert!(node.approx_eq(&GridCoord::new(reference.x, reference.y), 1e-9));
+        }
+
+        // The orientation and spacing are retained.
+        let (u, v) = grid.lattice_vectors();
+        assert!((u.norm() - 7.0).abs() < 1e-9 && (v.norm() - 5.0).abs() < 1e-9);
+        assert!((u.y.atan2(u.x).to_degrees() + 22.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_with_target_count() {
+        for (width, height, target) in [
+            (100.0, 80.0, 500),
+            (300.0, 200.0, 2000),
+            (64.0, 256.0, 1000),
+        ] {
+            for degrees in [0.0, 15.0, 45.0, 80.0] {
+                let count = GridPositionIterator::with_target_count(
+                    width,
+                    height,
+                    target,
+                    Angle::<f64>::from_degrees(degrees),
+                )
+                .count() as f64;
+
+                let error = (count - target as f64).abs() / target as f64;
+                assert!(
+                    error < 0.1,
+                    "{width}×{height} at {degrees}°: {count} of {target}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_am_dots() {
+        let grid = GridPositionIterator::new(
+            100.0,
+            50.0,
+            6.0,
+            8.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(0.0),
+        );
+
+        let mut dots: Vec<_> = grid.am_dots(|point| point.x / 100.0).collect();
+        dots.sort_by(|(a, _), (b, _)| (a.y, a.x).partial_cmp(&(b.y, b.x)).unwrap());
+        assert!(dots.iter().all(|(_, r)| (0.0..=3.0).contains(r)));
+
+        // The radii grow monotonically along each row.
+        for pair in dots.windows(2) {
+            let ((a, ra), (b, rb)) = (&pair[0], &pair[1]);
+            if a.y == b.y {
+                assert!(rb > ra, "{ra} at {a:?}, {rb} at {b:?}");
+            }
+        }
+
+        // The dot area is proportional to the tone.
+        let (coord, radius) = &dots[0];
+        assert_eq!(coord.x, 2.0);
+        assert!((radius * radius - 0.02 * 9.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_clamp_to_rect() {
+        let grid = |angle: f64| {
+            GridPositionIterator::new(
+                50.0,
+                40.0,
+                7.0,
+                7.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(angle),
+            )
+        };
+
+        for angle in [0.0, 30.0] {
+            let regular: Vec<_> = grid(angle).collect();
+            let clamped: Vec<_> = grid(angle).clamp_to_rect().collect();
+            assert!(clamped.len() > regular.len());
+            for coord in &clamped {
+                assert!((0.0..=50.0).contains(&coord.x) && (0.0..=40.0).contains(&coord.y));
+            }
+
+            // All regular positions are retained.
+            for coord in &regular {
+                assert!(clamped.iter().any(|c| c.approx_eq(coord, 1e-9)));
+            }
+        }
+
+        // The unrotated nodes at x = -3 and y = -1 are clamped onto the edges, such that
+        // the top edge receives 9 positions and the left edge 7, sharing the corner.
+        let on_edge = |coord: &GridCoord| coord.x == 0.0 || coord.y == 0.0;
+        assert!(!grid(0.0).any(|c| on_edge(&c)));
+        assert_eq!(grid(0.0).clamp_to_rect().filter(on_edge).count(), 9 + 7 - 1);
+    }
+
+    #[test]
+    fn test_clamp_to_rect_retains_settings() {
+        // The unrotated nodes at x = -2 and 62 as well as y = -4 and 44 are clamped;
+        // the diagonal nodes outside the corners are too far away.
+        let clamped: Vec<_> = grid(8.0, 0.0).clamp_to_rect().collect();
+        assert!(clamped.iter().any(|c| c.x == 60.0));
+        assert!(clamped.iter().any(|c| c.y == 40.0));
+
+        let first = grid(8.0, 0.0)
+            .with_start_corner(Corner::BottomRight)
+            .clamp_to_rect()
+            .next()
+            .unwrap();
+        assert_eq!(first, GridCoord::new(54.0, 40.0));
+
+        let exclusive: Vec<_> = grid(8.0, 0.0)
+            .with_boundary(Boundary::Exclusive)
+            .clamp_to_rect()
+            .collect();
+        assert!(exclusive.iter().all(|c| c.x < 60.0 && c.y < 40.0));
+        assert!(exclusive.iter().any(|c| c.x == 0.0));
+
+        let stride = grid(8.0, 0.0).with_stride(2, 1).clamp_to_rect().count();
+        assert!(stride < clamped.len());
+
+        // Wrapping snaps the spacing to 7.5, such that the nodes at x = 60 are skipped.
+        let wrapped: Vec<_> = grid(8.0, 0.0).with_wrap_x(true).clamp_to_rect().collect();
+        assert!(wrapped.iter().any(|c| c.x == 0.0));
+        assert!(wrapped.iter().all(|c| c.x < 60.0 - 1e-9));
+    }
+
+    #[test]
+    fn test_quarter_turn_matches_swapped_grid() {
+        // The nodes lie exactly on the edges of the rectangle.
+        let (width, height, dx, dy) = (60.0, 40.0, 10.0, 5.0);
+        let relative = |grid: GridPositionIterator, center: Vector| {
+            let mut coords: Vec<_> = grid.map(|c| (c.x - center.x, c.y - center.y)).collect();
+            coords.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            coords
+        };
+
+        for boundary in [Boundary::Inclusive, Boundary::Exclusive] {
+            let grid = |width: f64, height: f64, degrees: f64| {
+                GridPositionIterator::new(
+                    width,
+                    height,
+                    dx,
+                    dy,
+                    0.0,
+                    0.0,
+                    Angle::<f64>::from_degrees(degrees),
+                )
+                .with_boundary(boundary)
+            };
+
+            // At 0°, the grid is the plain lattice of the rectangle.
+            let expected = match boundary {
+                Boundary::Inclusive => 7 * 9,
+                Boundary::Exclusive => 6 * 8,
+            };
+            assert_eq!(grid(width, height, 0.0).count(), expected);
+            assert_eq!(grid(width, height, 0.0).exact_count(), expected);
+
+            // At 90°, the rotated grid space swaps the width and height; un-rotating the
+            // positions of the swapped unrotated grid maps (x, y) to (y, -x).
+            let center = Vector::new(width, height) * 0.5;
+            let rotated = relative(grid(width, height, 90.0), center);
+            let swapped = relative(grid(height, width, 0.0), Vector::new(height, width) * 0.5);
+            let mut expected: Vec<_> = swapped.iter().map(|&(x, y)| (y, -x)).collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            assert_eq!(rotated.len(), expected.len());
+            assert_eq!(grid(width, height, 90.0).exact_count(), expected.len());
+            assert_vec_eq!(
+                grid(width, height, 90.0).rotated_extent(),
+                Vector::new(height, width)
+            );
+            for (a, b) in rotated.iter().zip(&expected) {
+                assert!((a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dual() {
+        let grid = || {
+            GridPositionIterator::new(
+                80.0,
+                60.0,
+                8.0,
+                6.0,
+                1.0,
+                -2.0,
+                Angle::<f64>::from_degrees(25.0),
+            )
+        };
+
+        let original = grid();
+        let half_diagonal = 0.5 * (8.0_f64.powi(2) + 6.0_f64.powi(2)).sqrt();
+        let dual: Vec<_> = grid().dual().collect();
+        assert!(!dual.is_empty());
+        for coord in &dual {
+            let point = Vector::new(coord.x, coord.y);
+            let lattice = original.to_lattice(point);
+            assert!((lattice.x - lattice.x.floor() - 0.5).abs() < 1e-9);
+            assert!((lattice.y - lattice.y.floor() - 0.5).abs() < 1e-9);
+
+            let nearest = original.nearest(point);
+            let distance = Vector::new(nearest.x - coord.x, nearest.y - coord.y).norm();
+            assert!((distance - half_diagonal).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_is_empty() {
+        let grid = |x0: f64, y0: f64, angle: f64| {
+            GridPositionIterator::new(
+                2.0,
+                2.0,
+                10.0,
+                10.0,
+                x0,
+                y0,
+                Angle::<f64>::from_degrees(angle),
+            )
+        };
+
+        for angle in [0.0, 30.0] {
+            assert!(!grid(0.0, 0.0, angle).is_empty());
+            assert!(!grid(0.5, -0.5, angle).is_empty());
+            assert!(grid(5.0, 0.0, angle).is_empty());
+            assert!(grid(0.0, 5.0, angle).is_empty());
+        }
+
+        let mut grid = grid(0.0, 0.0, 0.0);
+        assert!(grid.next().is_some());
+        assert!(grid.is_empty());
+
+        let clipped = GridPositionIterator::new_cropped(
+            (100.0, 100.0),
+            (Vector::new(41.0, 41.0), Vector::new(49.0, 49.0)),
+            10.0,
+            10.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(0.0),
+        );
+        assert!(clipped.is_empty());
+    }
+
+    #[test]
+    fn test_registration_error() {
+        let grid = |x0: f64, y0: f64, angle: f64| {
+            GridPositionIterator::new(
+                60.0,
+                40.0,
+                8.0,
+                8.0,
+                x0,
+                y0,
+                Angle::<f64>::from_degrees(angle),
+            )
+        };
+
+        let center = Vector::new(30.0, 20.0);
+        let error = grid(0.0, 0.0, 35.0).registration_error(center).unwrap();
+        assert!(error.norm() < 1e-9);
+
+        // The error reflects the phase of the grid.
+        let error = grid(1.5, -2.0, 0.0).registration_error(center).unwrap();
+        assert!(error.approx_eq(&Vector::new(1.5, -2.0), 1e-9));
+        let error = grid(1.5, -2.0, 35.0).registration_error(center).unwrap();
+        assert!((error.norm() - 2.5).abs() < 1e-9);
+
+        // Targets outside the rectangle report the closest emitted position,
+        // rather than the closest node of the infinite lattice.
+        let target = Vector::new(-30.0, -30.0);
+        let error = grid(0.0, 0.0, 15.0).registration_error(target).unwrap();
+        let closest = grid(0.0, 0.0, 15.0)
+            .map(|c| Vector::new(c.x, c.y) - target)
+            .fold(Vector::new(f64::INFINITY, f64::INFINITY), |a, b| {
+                if b.norm_sq() < a.norm_sq() {
+                    b
+                } else {
+                    a
+                }
+            });
+        assert!(error.approx_eq(&closest, 1e-9));
+
+        // Positions outside the clip region are not considered.
+        let circle = Circle::new(Vector::new(30.0, 20.0), 10.0);
+        let clipped =
+            GridPositionIterator::new_clipped(&circle, 8.0, 8.0, 0.0, 0.0, Angle::default());
+        let error = clipped.registration_error(Vector::new(0.0, 20.0)).unwrap();
+        assert!(error.approx_eq(&Vector::new(22.0, 0.0), 1e-9));
+
+        let mut consumed = grid(0.0, 0.0, 15.0);
+        consumed.by_ref().for_each(drop);
+        assert_eq!(consumed.registration_error(center), None);
+    }
+
+    #[test]
+    fn test_debug_row() {
+        let alpha = Angle::<f64>::from_degrees(2.0);
+        let grid = GridPositionIterator::new(100.0, 10.0, 5.0, 5.0, 0.0, 0.0, alpha);
+        let (sin, cos) = alpha.sin_cos();
+
+        // The row through the center only crosses the short edges, at half the width
+        // of the rectangle (projected onto the row) from the center of the bounding box.
+        let half_extent = 0.5 * (100.0 * cos + 10.0 * sin);
+        let half_row = 50.0 / cos;
+        let row = grid.debug_row(5.0);
+        assert_eq!((row.top, row.bottom), (None, None));
+        assert!((row.left.unwrap() - (half_extent - half_row)).abs() < 1e-9);
+        assert!((row.right.unwrap() - (half_extent + half_row)).abs() < 1e-9);
+        assert_eq!((row.min_t, row.max_t), (row.left, row.right));
+
+        // Rows outside of the rectangle have no intersections.
+        let row = grid.debug_row(-5.0);
+        assert_eq!((row.min_t, row.max_t), (None, None));
     }
 }
@@ -0,0 +1,108 @@
+//! Contains the [`LineScreenIterator`] type.
+
+use crate::inner::line_segment::LineSegment;
+use crate::inner::optimal_iterator::OptimalIterator;
+use crate::inner::vector::Vector;
+use crate::Angle;
+
+/// An iterator for the lines of a rotated line screen.
+///
+/// Instead of a lattice of dots, a line screen consists of parallel lines at the
+/// screen angle. Each line is clipped to the rectangle; lines along the rectangle's
+/// edges are included.
+pub struct LineScreenIterator {
+    sin: f64,
+    cos: f64,
+    inner: OptimalIterator,
+}
+
+impl LineScreenIterator {
+    /// Creates a new iterator.
+    ///
+    /// ## Arguments
+    /// * `width` - The width of the screen. Must be positive.
+    /// * `height` - The height of the screen. Must be positive.
+    /// * `spacing` - The spacing of the lines. Must be positive and finite.
+    /// * `alpha` - The orientation of the lines. Must be in range 0..90°.
+    ///
+    /// ## Panics
+    /// Panics if the width or height is not positive, if the spacing is not positive and finite,
+    /// or if the angle is out of range.
+    pub fn new(width: f64, height: f64, spacing: f64, alpha: Angle<f64>) -> Self {
+        assert!(alpha.into_radians() >= 0.0);
+        assert!(alpha.into_radians() <= std::f64::consts::FRAC_PI_2);
+        assert!(width > 0.0);
+        assert!(height > 0.0);
+        assert!(
+            spacing.is_finite() && spacing > 0.0,
+            "the spacing must be positive and finite"
+        );
+
+        let tl = Vector::new(0.0, 0.0);
+        let tr = Vector::new(width, 0.0);
+        let bl = Vector::new(0.0, height);
+        let br = Vector::new(width, height);
+
+        let alpha = alpha.normalize_grid();
+        let (sin, cos) = alpha.sin_cos();
+
+        Self {
+            sin,
+            cos,
+            inner: OptimalIterator::new(tl, tr, bl, br, alpha, spacing, spacing, 0.0, 0.0),
+        }
+    }
+}
+
+impl Iterator for LineScreenIterator {
+    type Item = LineSegment;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, end) = self.inner.next_span()?;
+
+        // Un-rotate the line.
+        let center = *self.inner.center();
+        let start = start.rotate_around_with(&center, -self.sin, self.cos);
+        let end = end.rotate_around_with(&center, -self.sin, self.cos);
+        Some(LineSegment::from_points(start, &end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unrotated_screen() {
+        let lines: Vec<_> =
+            LineScreenIterator::new(60.0, 40.0, 10.0, Angle::from_degrees(0.0)).collect();
+        assert_eq!(lines.len(), 40 / 10 + 1);
+
+        for line in &lines {
+            let end = *line.start() + *line.direction();
+            assert_vec_eq!(*line.start(), Vector::new(0.0, end.y));
+            assert_vec_eq!(end, Vector::new(60.0, line.start().y));
+        }
+    }
+
+    #[test]
+    fn test_segments_lie_within_rectangle() {
+        let inside =
+            |p: Vector| p.x >= -1e-9 && p.x <= 60.0 + 1e-9 && p.y >= -1e-9 && p.y <= 40.0 + 1e-9;
+
+        let lines: Vec<_> =
+            LineScreenIterator::new(60.0, 40.0, 3.0, Angle::from_degrees(15.0)).collect();
+        assert!(!lines.is_empty());
+
+        for line in &lines {
+            assert!(inside(*line.start()));
+            assert!(inside(*line.start() + *line.direction()));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the spacing must be positive and finite")]
+    fn test_zero_spacing() {
+        LineScreenIterator::new(60.0, 40.0, 0.0, Angle::from_degrees(15.0));
+    }
+}
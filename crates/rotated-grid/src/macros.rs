@@ -0,0 +1,87 @@
+//! Contains assertion macros for approximate comparisons.
+
+/// Asserts that two [`Vector`](crate::inner::vector::Vector) values are approximately equal,
+/// i.e. that their coordinates differ by no more than the specified tolerance.
+/// The tolerance defaults to `1e-9`.
+///
+/// ## Example
+///
+/// ```
+/// use rotated_grid::assert_vec_eq;
+/// use rotated_grid::inner::vector::Vector;
+///
+/// assert_vec_eq!(Vector::new(0.1 + 0.2, 1.0), Vector::new(0.3, 1.0));
+/// assert_vec_eq!(Vector::new(1.001, 1.0), Vector::new(1.0, 1.0), 1e-2);
+/// ```
+#[macro_export]
+macro_rules! assert_vec_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_vec_eq!($left, $right, 1e-9)
+    };
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {{
+        let (left, right, tolerance) = (&$left, &$right, $tolerance);
+        if !left.approx_eq(right, tolerance) {
+            panic!(
+                "assertion `left ≈ right` failed (tolerance: {:?})\n  left: {:?}\n right: {:?}",
+                tolerance, left, right
+            );
+        }
+    }};
+}
+
+/// Asserts that two [`GridCoord`](crate::GridCoord) values are approximately equal,
+/// i.e. that their coordinates differ by no more than the specified tolerance.
+/// The tolerance defaults to `1e-9`.
+///
+/// ## Example
+///
+/// ```
+/// use rotated_grid::{assert_coord_eq, GridCoord};
+///
+/// assert_coord_eq!(GridCoord::new(0.1 + 0.2, 1.0), GridCoord::new(0.3, 1.0));
+/// assert_coord_eq!(GridCoord::new(1.001, 1.0), GridCoord::new(1.0, 1.0), 1e-2);
+/// ```
+#[macro_export]
+macro_rules! assert_coord_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_coord_eq!($left, $right, 1e-9)
+    };
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {{
+        let (left, right, tolerance) = (&$left, &$right, $tolerance);
+        if !left.approx_eq(right, tolerance) {
+            panic!(
+                "assertion `left ≈ right` failed (tolerance: {:?})\n  left: {:?}\n right: {:?}",
+                tolerance, left, right
+            );
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inner::vector::Vector;
+    use crate::GridCoord;
+
+    #[test]
+    fn test_within_tolerance() {
+        assert_vec_eq!(Vector::new(1.0, 2.0), Vector::new(1.0 + 1e-12, 2.0 - 1e-12));
+        assert_vec_eq!(Vector::new(1.0, 2.0), Vector::new(1.05, 1.95), 0.1);
+        assert_coord_eq!(
+            GridCoord::new(1.0, 2.0),
+            GridCoord::new(1.0 + 1e-12, 2.0 - 1e-12)
+        );
+        assert_coord_eq!(GridCoord::new(1.0, 2.0), GridCoord::new(1.05, 1.95), 0.1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_vector_beyond_tolerance() {
+        assert_vec_eq!(Vector::new(1.0, 2.0), Vector::new(1.0, 2.001), 1e-4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_coord_beyond_tolerance() {
+        assert_coord_eq!(GridCoord::new(1.0, 2.0), GridCoord::new(1.001, 2.0), 1e-4);
+    }
+}
@@ -0,0 +1,70 @@
+//! Contains the [`MappedGrid`] type.
+
+use crate::{GridCoord, GridPositionIterator};
+
+/// An iterator for the positions on a rotated grid, transformed by a closure.
+///
+/// See [`GridPositionIterator::map_coord`].
+pub struct MappedGrid<F> {
+    grid: GridPositionIterator,
+    f: F,
+}
+
+impl<F> MappedGrid<F>
+where
+    F: FnMut(GridCoord) -> GridCoord,
+{
+    pub(crate) fn new(grid: GridPositionIterator, f: F) -> Self {
+        Self { grid, f }
+    }
+
+    /// Determines the exact number of remaining grid positions without advancing the iterator.
+    ///
+    /// See [`GridPositionIterator::exact_count`].
+    pub fn exact_count(&self) -> usize {
+        self.grid.exact_count()
+    }
+}
+
+impl<F> Iterator for MappedGrid<F>
+where
+    F: FnMut(GridCoord) -> GridCoord,
+{
+    type Item = GridCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.grid.next().map(&mut self.f)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.grid.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Angle, GridCoord, GridPositionIterator};
+
+    #[test]
+    fn test_negate() {
+        let grid = || {
+            GridPositionIterator::new(
+                50.0,
+                40.0,
+                7.0,
+                7.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(20.0),
+            )
+        };
+
+        let mapped = grid().map_coord(|c| GridCoord::new(-c.x, -c.y));
+        assert_eq!(mapped.exact_count(), grid().exact_count());
+
+        let mapped: Vec<_> = mapped.collect();
+        let expected: Vec<_> = grid().map(|c| GridCoord::new(-c.x, -c.y)).collect();
+        assert!(!mapped.is_empty());
+        assert_eq!(mapped, expected);
+    }
+}
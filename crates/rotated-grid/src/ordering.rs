@@ -0,0 +1,111 @@
+//! Contains alternative orders of the grid positions.
+
+use crate::inner::vector::Vector;
+use crate::{GridCoord, GridPositionIterator};
+
+impl GridPositionIterator {
+    /// Collects the remaining grid positions and orders them along a Z-order (Morton) curve
+    /// of their pixel positions, improving cache locality when writing them into a buffer.
+    ///
+    /// Since the positions need to be sorted, this allocates a vector holding all of them.
+    /// Positions with negative coordinates are treated as lying on pixel zero.
+    pub fn morton_order(self) -> Vec<GridCoord> {
+        let mut coords: Vec<_> = self.collect();
+        coords.sort_by_cached_key(|coord| morton_code(coord.x as u32, coord.y as u32));
+        coords
+    }
+
+    /// Iterates the remaining grid positions column by column rather than row by row,
+    /// e.g. for writing into column-major image buffers.
+    ///
+    /// The columns are the lattice columns along the (rotated) Y axis, ordered from left
+    /// to right, and each column is visited from top to bottom; for unrotated grids,
+    /// this is the column-major order of the pixel positions. Since the underlying iteration
+    /// is row-oriented, this allocates a vector holding all positions in order to sort them.
+    pub fn column_major(mut self) -> impl Iterator<Item = GridCoord> {
+        let mut coords: Vec<_> = self.by_ref().collect();
+        coords.sort_by_cached_key(|coord| {
+            let lattice = self.to_lattice(Vector::new(coord.x, coord.y));
+            (lattice.x.round() as i64, lattice.y.round() as i64)
+        });
+        coords.into_iter()
+    }
+}
+
+/// Interleaves the bits of the X and Y coordinates into a Z-order (Morton) code.
+fn morton_code(x: u32, y: u32) -> u64 {
+    fn spread(value: u32) -> u64 {
+        let mut value = value as u64;
+        value = (value | (value << 16)) & 0x0000_FFFF_0000_FFFF;
+        value = (value | (value << 8)) & 0x00FF_00FF_00FF_00FF;
+        value = (value | (value << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+        value = (value | (value << 2)) & 0x3333_3333_3333_3333;
+        value = (value | (value << 1)) & 0x5555_5555_5555_5555;
+        value
+    }
+
+    spread(x) | (spread(y) << 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Angle;
+
+    #[test]
+    fn test_morton_code() {
+        assert_eq!(morton_code(0, 0), 0);
+        assert_eq!(morton_code(1, 0), 1);
+        assert_eq!(morton_code(0, 1), 2);
+        assert_eq!(morton_code(3, 3), 15);
+        assert_eq!(morton_code(4, 0), 16);
+    }
+
+    #[test]
+    fn test_morton_order() {
+        let grid = || {
+            GridPositionIterator::new(
+                256.0,
+                256.0,
+                4.0,
+                4.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(15.0),
+            )
+        };
+
+        let by_position = |a: &GridCoord, b: &GridCoord| {
+            (a.y, a.x)
+                .partial_cmp(&(b.y, b.x))
+                .expect("coordinates are finite")
+        };
+        let mut expected: Vec<_> = grid().collect();
+        let mut ordered = grid().morton_order();
+        assert_eq!(ordered.len(), expected.len());
+
+        let mean_step = ordered
+            .windows(2)
+            .map(|w| ((w[1].x - w[0].x).powi(2) + (w[1].y - w[0].y).powi(2)).sqrt())
+            .sum::<f64>()
+            / (ordered.len() - 1) as f64;
+        assert!(mean_step < 3.0 * 4.0, "mean step was {mean_step}");
+
+        expected.sort_by(by_position);
+        ordered.sort_by(by_position);
+        assert_eq!(ordered, expected);
+    }
+
+    #[test]
+    fn test_column_major() {
+        let grid =
+            || GridPositionIterator::new(60.0, 40.0, 7.0, 5.0, 0.0, 0.0, Angle::<f64>::default());
+
+        let columns: Vec<_> = grid().column_major().collect();
+        let mut expected: Vec<_> = grid().collect();
+        assert_eq!(columns.len(), expected.len());
+
+        expected.sort_by(|a, b| (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap());
+        assert_eq!(columns, expected);
+    }
+}
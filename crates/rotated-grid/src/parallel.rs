@@ -0,0 +1,98 @@
+//! Contains the [`ParallelGrid`] type for iterating grid positions with rayon.
+
+use crate::inner::optimal_iterator::OptimalXIterator;
+use crate::inner::vector::Vector;
+use crate::{GridCoord, GridPositionIterator};
+use rayon::iter::plumbing::UnindexedConsumer;
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// A parallel iterator for the positions on a rotated grid.
+///
+/// The remaining rows of the grid are determined up front and then distributed across
+/// the threads of the rayon thread pool, such that each row is produced by a single thread.
+/// When collected, the positions appear in the same order as with the sequential iterator.
+///
+/// See [`GridPositionIterator::into_par_iter`](IntoParallelIterator::into_par_iter).
+pub struct ParallelGrid {
+    grid: Arc<GridPositionIterator>,
+    rows: Vec<(f64, OptimalXIterator)>,
+}
+
+impl IntoParallelIterator for GridPositionIterator {
+    type Iter = ParallelGrid;
+    type Item = GridCoord;
+
+    fn into_par_iter(self) -> Self::Iter {
+        let rows = self.inner.remaining_rows().collect();
+        ParallelGrid {
+            grid: Arc::new(self),
+            rows,
+        }
+    }
+}
+
+impl ParallelIterator for ParallelGrid {
+    type Item = GridCoord;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        let grid = self.grid;
+        self.rows
+            .into_par_iter()
+            .flat_map_iter(move |(y, xs)| {
+                let grid = Arc::clone(&grid);
+                xs.filter_map(move |x| grid.emit(Vector::new(x, y)))
+            })
+            .drive_unindexed(consumer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Angle, GridCoord, GridPositionIterator};
+    use rayon::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    fn grid() -> GridPositionIterator {
+        GridPositionIterator::new(
+            200.0,
+            150.0,
+            7.0,
+            7.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(15.0),
+        )
+    }
+
+    #[test]
+    fn test_for_each_visits_every_position_once() {
+        let counter = AtomicUsize::new(0);
+        let visited = Mutex::new(Vec::new());
+        grid().into_par_iter().for_each(|coord| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            visited.lock().unwrap().push(coord);
+        });
+
+        let mut expected: Vec<_> = grid().collect();
+        let mut visited = visited.into_inner().unwrap();
+        let by_position =
+            |a: &GridCoord, b: &GridCoord| (a.y, a.x).partial_cmp(&(b.y, b.x)).unwrap();
+        expected.sort_by(by_position);
+        visited.sort_by(by_position);
+
+        assert_eq!(counter.load(Ordering::Relaxed), grid().exact_count());
+        assert_eq!(visited, expected);
+    }
+
+    #[test]
+    fn test_collect_preserves_order() {
+        let parallel: Vec<_> = grid().into_par_iter().collect();
+        let sequential: Vec<_> = grid().collect();
+        assert_eq!(parallel, sequential);
+    }
+}
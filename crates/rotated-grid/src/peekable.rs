@@ -0,0 +1,108 @@
+//! Contains the [`PeekableGrid`] type.
+
+use crate::{GridCoord, GridPositionIterator};
+
+/// An iterator for positions on a rotated grid that allows peeking at the next position
+/// while still providing the exact number of remaining positions.
+///
+/// See [`GridPositionIterator::peekable_counted`].
+pub struct PeekableGrid {
+    grid: GridPositionIterator,
+    peeked: Option<Option<GridCoord>>,
+}
+
+impl PeekableGrid {
+    pub(crate) fn new(grid: GridPositionIterator) -> Self {
+        Self { grid, peeked: None }
+    }
+
+    /// Returns a reference to the next position without advancing the iterator.
+    pub fn peek(&mut self) -> Option<&GridCoord> {
+        let grid = &mut self.grid;
+        self.peeked.get_or_insert_with(|| grid.next()).as_ref()
+    }
+
+    /// Determines the exact number of remaining positions, including a peeked one,
+    /// without advancing the iterator.
+    ///
+    /// See [`GridPositionIterator::exact_count`].
+    pub fn remaining(&self) -> usize {
+        match &self.peeked {
+            Some(Some(_)) => 1 + self.grid.exact_count(),
+            Some(None) => 0,
+            None => self.grid.exact_count(),
+        }
+    }
+}
+
+impl Iterator for PeekableGrid {
+    type Item = GridCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.grid.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Unlike `remaining`, this does not visit the remaining rows.
+        let peeked = match &self.peeked {
+            Some(Some(_)) => 1,
+            Some(None) => return (0, Some(0)),
+            None => 0,
+        };
+
+        let (lower, upper) = self.grid.size_hint();
+        (
+            lower.saturating_add(peeked),
+            upper.and_then(|upper| upper.checked_add(peeked)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Angle, GridPositionIterator};
+
+    #[test]
+    fn test_peek_preserves_sequence_and_count() {
+        let grid = || {
+            GridPositionIterator::new(
+                60.0,
+                45.0,
+                8.0,
+                8.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(30.0),
+            )
+        };
+        let expected: Vec<_> = grid().collect();
+
+        let mut peekable = grid().peekable_counted();
+        let mut actual = Vec::new();
+        loop {
+            assert_eq!(peekable.remaining(), expected.len() - actual.len());
+            let peeked = peekable.peek().cloned();
+            assert_eq!(peekable.peek().cloned(), peeked);
+            assert_eq!(peekable.remaining(), expected.len() - actual.len());
+
+            let (lower, upper) = peekable.size_hint();
+            assert!(lower <= peekable.remaining());
+            assert!(upper.unwrap() >= peekable.remaining());
+
+            let next = peekable.next();
+            assert_eq!(next, peeked);
+            match next {
+                Some(coord) => actual.push(coord),
+                None => break,
+            }
+        }
+
+        assert_eq!(actual, expected);
+        assert_eq!(peekable.remaining(), 0);
+        assert!(peekable.peek().is_none());
+        assert_eq!(peekable.size_hint(), (0, Some(0)));
+    }
+}
@@ -0,0 +1,452 @@
+//! Contains the [`ClipRegion`] trait and its implementors.
+
+use crate::inner::line_segment::LineSegment;
+use crate::inner::vector::{signed_triangle_area, Vector};
+use crate::GridError;
+
+/// A region restricting the positions of a grid created by
+/// [`GridPositionIterator::new_clipped`](crate::GridPositionIterator::new_clipped).
+pub trait ClipRegion {
+    /// Determines whether the point lies within the region.
+    fn contains(&self, point: Vector) -> bool;
+
+    /// Returns the minimum and maximum corner of the axis-aligned bounding box of the region.
+    fn bounds(&self) -> (Vector, Vector);
+}
+
+/// An axis-aligned rectangle, including its edges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rectangle {
+    min: Vector,
+    max: Vector,
+}
+
+impl Rectangle {
+    /// Constructs a rectangle from its minimum and maximum corner.
+    pub fn new(min: Vector, max: Vector) -> Self {
+        Self { min, max }
+    }
+}
+
+impl ClipRegion for Rectangle {
+    fn contains(&self, point: Vector) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+    }
+
+    fn bounds(&self) -> (Vector, Vector) {
+        (self.min, self.max)
+    }
+}
+
+/// A disk, including its circumference.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Circle {
+    center: Vector,
+    radius: f64,
+}
+
+impl Circle {
+    /// Constructs a disk from its center and radius.
+    pub fn new(center: Vector, radius: f64) -> Self {
+        Self { center, radius }
+    }
+}
+
+impl ClipRegion for Circle {
+    fn contains(&self, point: Vector) -> bool {
+        (point - self.center).norm_sq() <= self.radius * self.radius
+    }
+
+    fn bounds(&self) -> (Vector, Vector) {
+        let radius = Vector::new(self.radius, self.radius);
+        (self.center - radius, self.center + radius)
+    }
+}
+
+/// An axis-aligned ellipse, including its circumference.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ellipse {
+    center: Vector,
+    radii: Vector,
+}
+
+impl Ellipse {
+    /// Constructs an ellipse from its center and its radii along the X and Y axis.
+    ///
+    /// ## Panics
+    /// Panics if either radius is not positive.
+    pub fn new(center: Vector, radii: Vector) -> Self {
+        assert!(radii.x > 0.0 && radii.y > 0.0);
+        Self { center, radii }
+    }
+}
+
+impl ClipRegion for Ellipse {
+    fn contains(&self, point: Vector) -> bool {
+        let x = (point.x - self.center.x) / self.radii.x;
+        let y = (point.y - self.center.y) / self.radii.y;
+        x * x + y * y <= 1.0
+    }
+
+    fn bounds(&self) -> (Vector, Vector) {
+        (self.center - self.radii, self.center + self.radii)
+    }
+}
+
+/// A convex polygon, including its edges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvexPolygon {
+    vertices: Vec<Vector>,
+}
+
+impl ConvexPolygon {
+    /// Constructs a polygon from its vertices, in either winding order.
+    ///
+    /// ## Panics
+    /// Panics if fewer than three vertices are specified or if the polygon is not convex.
+    /// See [`ConvexPolygon::try_new`] for a non-panicking alternative.
+    pub fn new(vertices: Vec<Vector>) -> Self {
+        assert!(vertices.len() >= 3);
+        assert!(is_convex(&vertices), "the polygon must be convex");
+        Self { vertices }
+    }
+
+    /// Constructs a polygon from its vertices, in either winding order, validating its convexity.
+    ///
+    /// ## Errors
+    /// Returns [`GridError::NonConvexPolygon`] if fewer than three vertices are specified
+    /// or if the polygon is not convex.
+    pub fn try_new(vertices: Vec<Vector>) -> Result<Self, GridError> {
+        if vertices.len() < 3 || !is_convex(&vertices) {
+            return Err(GridError::NonConvexPolygon);
+        }
+
+        Ok(Self { vertices })
+    }
+}
+
+/// Determines whether a polygon is convex, i.e. whether all consecutive vertices turn
+/// in the same direction. Collinear vertices are permitted.
+fn is_convex(vertices: &[Vector]) -> bool {
+    let (mut left, mut right) = (false, false);
+    for (index, &a) in vertices.iter().enumerate() {
+        let b = vertices[(index + 1) % vertices.len()];
+        let c = vertices[(index + 2) % vertices.len()];
+        let area = signed_triangle_area(a, b, c);
+        left |= area > 0.0;
+        right |= area < 0.0;
+    }
+
+    !(left && right)
+}
+
+impl ClipRegion for ConvexPolygon {
+    fn contains(&self, point: Vector) -> bool {
+        polygon_contains(&self.vertices, point)
+    }
+
+    fn bounds(&self) -> (Vector, Vector) {
+        polygon_bounds(&self.vertices)
+    }
+}
+
+/// A simple polygon, including its edges.
+///
+/// The polygon may be concave; points are evaluated using the even-odd rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    vertices: Vec<Vector>,
+}
+
+impl Polygon {
+    /// Constructs a polygon from its vertices, in either winding order.
+    ///
+    /// ## Panics
+    /// Panics if fewer than three vertices are specified.
+    pub fn new(vertices: Vec<Vector>) -> Self {
+        assert!(vertices.len() >= 3);
+        Self { vertices }
+    }
+}
+
+impl ClipRegion for Polygon {
+    fn contains(&self, point: Vector) -> bool {
+        polygon_contains(&self.vertices, point)
+    }
+
+    fn bounds(&self) -> (Vector, Vector) {
+        polygon_bounds(&self.vertices)
+    }
+}
+
+/// Determines the minimum and maximum corner of the axis-aligned bounding box of a polygon.
+fn polygon_bounds(vertices: &[Vector]) -> (Vector, Vector) {
+    vertices.iter().fold(
+        (
+            Vector::new(f64::INFINITY, f64::INFINITY),
+            Vector::new(f64::NEG_INFINITY, f64::NEG_INFINITY),
+        ),
+        |(min, max), vertex| {
+            (
+                Vector::new(min.x.min(vertex.x), min.y.min(vertex.y)),
+                Vector::new(max.x.max(vertex.x), max.y.max(vertex.y)),
+            )
+        },
+    )
+}
+
+/// Determines whether a point lies within a simple polygon using the even-odd rule.
+/// Points on the polygon's edges are considered inside.
+///
+/// A horizontal ray is cast from the point towards positive X and its intersections with
+/// the polygon's edges are counted. Each edge is treated as half-open along Y, such that
+/// vertices on the ray are counted exactly once and horizontal edges are skipped.
+fn polygon_contains(vertices: &[Vector], point: Vector) -> bool {
+    const EPSILON: f64 = 1e-9;
+
+    let mut inside = false;
+    for (index, start) in vertices.iter().enumerate() {
+        let end = &vertices[(index + 1) % vertices.len()];
+        let edge = LineSegment::from_points(*start, end);
+
+        if on_segment(&edge, point, EPSILON) {
+            return true;
+        }
+
+        // Skip edges not spanning the ray, including horizontal ones.
+        if (start.y > point.y) == (end.y > point.y) {
+            continue;
+        }
+
+        // The edge spans the ray, so it crosses the ray's line exactly once
+        // and only the side of the crossing along the ray matters.
+        let x = start.x + (point.y - start.y) * (end.x - start.x) / (end.y - start.y);
+        if x > point.x {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+/// Determines whether a point lies on a line segment, within the specified tolerance.
+fn on_segment(segment: &LineSegment, point: Vector, epsilon: f64) -> bool {
+    let direction = *segment.direction();
+    let delta = point - *segment.start();
+    let length_sq = direction.norm_sq();
+    if length_sq < epsilon * epsilon {
+        return delta.norm() < epsilon;
+    }
+
+    let t = delta.dot(&direction) / length_sq;
+    let distance = direction.cross(&delta).abs() / length_sq.sqrt();
+    distance < epsilon && (0.0..=1.0).contains(&t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Angle, GridPositionIterator};
+
+    /// A ring between two concentric circles.
+    #[derive(Clone)]
+    struct Annulus {
+        center: Vector,
+        inner: f64,
+        outer: f64,
+    }
+
+    impl ClipRegion for Annulus {
+        fn contains(&self, point: Vector) -> bool {
+            let distance = (point - self.center).norm();
+            self.inner <= distance && distance <= self.outer
+        }
+
+        fn bounds(&self) -> (Vector, Vector) {
+            Circle::new(self.center, self.outer).bounds()
+        }
+    }
+
+    #[test]
+    fn test_custom_region() {
+        let region = Annulus {
+            center: Vector::new(50.0, 40.0),
+            inner: 10.0,
+            outer: 30.0,
+        };
+        let angle = Angle::<f64>::from_degrees(20.0);
+
+        let clipped = GridPositionIterator::new_clipped(&region, 4.0, 4.0, 0.0, 0.0, angle);
+        let count = clipped.exact_count();
+        let coords: Vec<_> = clipped.collect();
+        assert_eq!(coords.len(), count);
+        assert!(coords
+            .iter()
+            .all(|c| region.contains(Vector::new(c.x, c.y))));
+
+        // The positions are those of the grid over the bounds within the region.
+        let (min, max) = region.bounds();
+        let extent = max - min;
+        let expected =
+            GridPositionIterator::new_at(min, extent.x, extent.y, 4.0, 4.0, 0.0, 0.0, angle)
+                .filter(|c| region.contains(Vector::new(c.x, c.y)))
+                .count();
+        assert_eq!(coords.len(), expected);
+        assert!(!coords
+            .iter()
+            .any(|c| (Vector::new(c.x, c.y) - region.center).norm() < 10.0));
+    }
+
+    #[test]
+    fn test_builtin_regions() {
+        let ellipse = Ellipse::new(Vector::new(0.0, 0.0), Vector::new(4.0, 2.0));
+        assert!(ellipse.contains(Vector::new(4.0, 0.0)));
+        assert!(ellipse.contains(Vector::new(0.0, -2.0)));
+        assert!(!ellipse.contains(Vector::new(3.0, 1.5)));
+
+        let triangle = ConvexPolygon::new(vec![
+            Vector::new(0.0, 0.0),
+            Vector::new(10.0, 0.0),
+            Vector::new(0.0, 10.0),
+        ]);
+        assert!(triangle.contains(Vector::new(2.0, 2.0)));
+        assert!(!triangle.contains(Vector::new(6.0, 6.0)));
+        assert_eq!(
+            triangle.bounds(),
+            (Vector::new(0.0, 0.0), Vector::new(10.0, 10.0))
+        );
+
+        let rectangle = Rectangle::new(Vector::new(1.0, 2.0), Vector::new(3.0, 4.0));
+        assert!(rectangle.contains(Vector::new(3.0, 2.0)));
+        assert!(!rectangle.contains(Vector::new(0.0, 3.0)));
+    }
+
+    #[test]
+    fn test_convex_polygon_try_new() {
+        let square = vec![
+            Vector::new(0.0, 0.0),
+            Vector::new(10.0, 0.0),
+            Vector::new(10.0, 10.0),
+            Vector::new(0.0, 10.0),
+        ];
+        assert!(ConvexPolygon::try_new(square.clone()).is_ok());
+
+        let mut reversed = square;
+        reversed.reverse();
+        assert!(ConvexPolygon::try_new(reversed).is_ok());
+
+        let arrow = vec![
+            Vector::new(0.0, 0.0),
+            Vector::new(10.0, 5.0),
+            Vector::new(0.0, 10.0),
+            Vector::new(4.0, 5.0),
+        ];
+        assert_eq!(
+            ConvexPolygon::try_new(arrow).err(),
+            Some(GridError::NonConvexPolygon)
+        );
+
+        let segment = vec![Vector::new(0.0, 0.0), Vector::new(10.0, 0.0)];
+        assert_eq!(
+            ConvexPolygon::try_new(segment).err(),
+            Some(GridError::NonConvexPolygon)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the polygon must be convex")]
+    fn test_convex_polygon_rejects_concave() {
+        ConvexPolygon::new(vec![
+            Vector::new(0.0, 0.0),
+            Vector::new(10.0, 5.0),
+            Vector::new(0.0, 10.0),
+            Vector::new(4.0, 5.0),
+        ]);
+    }
+
+    fn l_shape() -> Vec<Vector> {
+        vec![
+            Vector::new(0.0, 0.0),
+            Vector::new(40.0, 0.0),
+            Vector::new(40.0, 20.0),
+            Vector::new(20.0, 20.0),
+            Vector::new(20.0, 40.0),
+            Vector::new(0.0, 40.0),
+        ]
+    }
+
+    #[test]
+    fn test_clipped_constructors() {
+        let angle = Angle::<f64>::from_degrees(20.0);
+
+        let polygon =
+            GridPositionIterator::new_in_polygon_general(&l_shape(), 4.0, 4.0, 0.0, 0.0, angle);
+        let clipped =
+            GridPositionIterator::new_clipped(&Polygon::new(l_shape()), 4.0, 4.0, 0.0, 0.0, angle);
+        assert_eq!(polygon.collect::<Vec<_>>(), clipped.collect::<Vec<_>>());
+
+        let center = Vector::new(30.0, 20.0);
+        let circle = GridPositionIterator::new_in_circle(center, 15.0, 4.0, 4.0, 0.0, 0.0, angle);
+        let clipped = GridPositionIterator::new_clipped(
+            &Circle::new(center, 15.0),
+            4.0,
+            4.0,
+            0.0,
+            0.0,
+            angle,
+        );
+        assert_eq!(circle.collect::<Vec<_>>(), clipped.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_concave_polygon() {
+        let vertices = l_shape();
+        assert!(polygon_contains(&vertices, Vector::new(10.0, 10.0)));
+        assert!(polygon_contains(&vertices, Vector::new(30.0, 10.0)));
+        assert!(polygon_contains(&vertices, Vector::new(10.0, 30.0)));
+        assert!(!polygon_contains(&vertices, Vector::new(30.0, 30.0)));
+        assert!(!polygon_contains(&vertices, Vector::new(-5.0, 10.0)));
+    }
+
+    #[test]
+    fn test_winding_order() {
+        let mut vertices = l_shape();
+        vertices.reverse();
+        assert!(polygon_contains(&vertices, Vector::new(30.0, 10.0)));
+        assert!(!polygon_contains(&vertices, Vector::new(30.0, 30.0)));
+    }
+
+    #[test]
+    fn test_edges_and_vertices() {
+        let vertices = l_shape();
+
+        // Points on edges and vertices are inside.
+        assert!(polygon_contains(&vertices, Vector::new(20.0, 30.0)));
+        assert!(polygon_contains(&vertices, Vector::new(30.0, 20.0)));
+        assert!(polygon_contains(&vertices, Vector::new(20.0, 20.0)));
+        assert!(polygon_contains(&vertices, Vector::new(0.0, 0.0)));
+
+        // Rays passing through vertices or along horizontal edges are counted correctly.
+        assert!(polygon_contains(&vertices, Vector::new(10.0, 20.0)));
+        assert!(!polygon_contains(&vertices, Vector::new(-10.0, 20.0)));
+        assert!(!polygon_contains(&vertices, Vector::new(-10.0, 0.0)));
+    }
+
+    #[test]
+    fn test_shallow_edges() {
+        // The long top edge rises by only 2e-4 over its length, such that it is nearly
+        // parallel to the ray but still crosses it.
+        let vertices = vec![
+            Vector::new(0.0, -1e-4),
+            Vector::new(1000.0, 1e-4),
+            Vector::new(1000.0, -1.0),
+            Vector::new(0.0, -1.0),
+        ];
+        assert!(!polygon_contains(&vertices, Vector::new(200.0, 0.0)));
+        assert!(polygon_contains(&vertices, Vector::new(800.0, 0.0)));
+        assert!(polygon_contains(&vertices, Vector::new(200.0, -0.5)));
+    }
+}
@@ -0,0 +1,241 @@
+//! Contains diagnostics for the rosette formed by multiple halftone screens.
+
+use crate::{Angle, GridPositionIterator};
+
+/// The smallest separation between two screens not considered to produce moiré.
+/// This is the separation conventionally used for the low-contrast yellow channel.
+const MIN_SEPARATION_DEGREES: f64 = 15.0;
+
+/// The ideal separation between two screens.
+const IDEAL_SEPARATION_DEGREES: f64 = 30.0;
+
+/// The separation below which two screens are considered effectively identical.
+const DUPLICATE_SEPARATION_DEGREES: f64 = 5.0;
+
+/// The tolerance applied when comparing separations.
+const TOLERANCE_DEGREES: f64 = 1e-6;
+
+/// The separation between the screen angles of two channels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairSeparation {
+    /// The index of the first channel.
+    pub first: usize,
+    /// The index of the second channel.
+    pub second: usize,
+    /// The effective separation of the two screens, in range 0..=45°.
+    pub separation: Angle<f64>,
+    /// The deviation of the separation from the ideal separation of 30°.
+    pub deviation: Angle<f64>,
+    /// Whether the screens are too close to each other, producing visible moiré.
+    pub too_close: bool,
+}
+
+/// The result of [`rosette_quality`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RosetteReport {
+    /// The separations of all pairs of channels.
+    pub pairs: Vec<PairSeparation>,
+    /// Whether no pair of channels is too close, forming a clean rosette.
+    pub is_clean: bool,
+}
+
+/// Determines whether the screens at the specified angles form a clean rosette.
+///
+/// Since square screens repeat every 90°, the separation of two screens is their
+/// angle difference folded into the range 0..=45°; e.g. screens at 15° and 75° are
+/// separated by 30°. Pairs separated by less than 15° are flagged as producing moiré.
+pub fn rosette_quality(angles: &[Angle<f64>]) -> RosetteReport {
+    let mut pairs = Vec::new();
+    for (first, a) in angles.iter().enumerate() {
+        for (second, b) in angles.iter().enumerate().skip(first + 1) {
+            let separation = screen_separation(a, b);
+            pairs.push(PairSeparation {
+                first,
+                second,
+                separation: Angle::from_degrees(separation),
+                deviation: Angle::from_degrees((separation - IDEAL_SEPARATION_DEGREES).abs()),
+                too_close: separation < MIN_SEPARATION_DEGREES - TOLERANCE_DEGREES,
+            });
+        }
+    }
+
+    let is_clean = pairs.iter().all(|pair| !pair.too_close);
+    RosetteReport { pairs, is_clean }
+}
+
+/// A warning about two channels whose screens are effectively at the same angle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AngleWarning {
+    /// The index of the first channel.
+    pub first: usize,
+    /// The index of the second channel.
+    pub second: usize,
+    /// The effective separation of the two screens, in range 0..=45°.
+    pub separation: Angle<f64>,
+}
+
+/// The screen angles of the four channels of a CMYK halftone.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CmykScreens {
+    /// The screen angle of the cyan channel.
+    pub cyan: Angle<f64>,
+    /// The screen angle of the magenta channel.
+    pub magenta: Angle<f64>,
+    /// The screen angle of the yellow channel.
+    pub yellow: Angle<f64>,
+    /// The screen angle of the black channel.
+    pub black: Angle<f64>,
+}
+
+impl CmykScreens {
+    /// Creates the screens from the angles of the cyan, magenta, yellow and black channels.
+    pub fn new(
+        cyan: Angle<f64>,
+        magenta: Angle<f64>,
+        yellow: Angle<f64>,
+        black: Angle<f64>,
+    ) -> Self {
+        Self {
+            cyan,
+            magenta,
+            yellow,
+            black,
+        }
+    }
+
+    /// Creates the conventional screens at 15° (cyan), 75° (magenta), 0° (yellow)
+    /// and 45° (black).
+    pub fn standard() -> Self {
+        Self::new(
+            Angle::from_degrees(15.0),
+            Angle::from_degrees(75.0),
+            Angle::from_degrees(0.0),
+            Angle::from_degrees(45.0),
+        )
+    }
+
+    /// Returns the screen angles in channel order, i.e. cyan, magenta, yellow and black.
+    pub fn angles(&self) -> [Angle<f64>; 4] {
+        [self.cyan, self.magenta, self.yellow, self.black]
+    }
+
+    /// Validates that no two channels use effectively the same screen angle.
+    ///
+    /// Unlike [`rosette_quality`], which flags every pair separated by less than 15°,
+    /// this only warns about pairs separated by less than 5°, i.e. screens that are
+    /// almost certainly a configuration mistake. The channels of the warnings are given
+    /// by their index in [`CmykScreens::angles`]. An empty result means the angles are valid.
+    pub fn validate_angles(&self) -> Vec<AngleWarning> {
+        validate_angles(&self.angles())
+    }
+
+    /// Creates the grids of the four channels in channel order, sharing all parameters
+    /// but the angle. See [`GridPositionIterator::new`] for a description of the arguments.
+    pub fn grids(
+        &self,
+        width: f64,
+        height: f64,
+        dx: f64,
+        dy: f64,
+        x0: f64,
+        y0: f64,
+    ) -> [GridPositionIterator; 4] {
+        self.angles()
+            .map(|angle| GridPositionIterator::new(width, height, dx, dy, x0, y0, angle))
+    }
+}
+
+impl Default for CmykScreens {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// Determines the pairs of screen angles separated by less than 5°.
+fn validate_angles(angles: &[Angle<f64>]) -> Vec<AngleWarning> {
+    let mut warnings = Vec::new();
+    for (first, a) in angles.iter().enumerate() {
+        for (second, b) in angles.iter().enumerate().skip(first + 1) {
+            let separation = screen_separation(a, b);
+            if separation < DUPLICATE_SEPARATION_DEGREES - TOLERANCE_DEGREES {
+                warnings.push(AngleWarning {
+                    first,
+                    second,
+                    separation: Angle::from_degrees(separation),
+                });
+            }
+        }
+    }
+    warnings
+}
+
+/// Determines the separation of two square screens in degrees, in range 0..=45°.
+pub(crate) fn screen_separation(a: &Angle<f64>, b: &Angle<f64>) -> f64 {
+    let difference = (a.into_radians() - b.into_radians()).to_degrees();
+    let difference = difference.rem_euclid(90.0);
+    difference.min(90.0 - difference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn angles(degrees: &[f64]) -> Vec<Angle<f64>> {
+        degrees.iter().map(|&d| Angle::from_degrees(d)).collect()
+    }
+
+    #[test]
+    fn test_standard_set_is_clean() {
+        let report = rosette_quality(&angles(&[15.0, 75.0, 0.0, 45.0]));
+        assert_eq!(report.pairs.len(), 6);
+        assert!(report.is_clean);
+
+        let cyan_magenta = &report.pairs[0];
+        assert!((cyan_magenta.separation.into_radians().to_degrees() - 30.0).abs() < 1e-9);
+        assert!(cyan_magenta.deviation.into_radians().abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_close_pair_is_flagged() {
+        let report = rosette_quality(&angles(&[15.0, 20.0, 0.0, 45.0]));
+        assert!(!report.is_clean);
+
+        let flagged: Vec<_> = report
+            .pairs
+            .iter()
+            .filter(|pair| pair.too_close)
+            .map(|pair| (pair.first, pair.second))
+            .collect();
+        assert_eq!(flagged, vec![(0, 1)]);
+    }
+
+    #[test]
+    fn test_validate_standard_set() {
+        assert!(CmykScreens::standard().validate_angles().is_empty());
+    }
+
+    #[test]
+    fn test_validate_duplicate_angles() {
+        // Magenta duplicates cyan, black is equivalent to yellow modulo 90°.
+        let screens = CmykScreens::new(
+            Angle::from_degrees(15.0),
+            Angle::from_degrees(17.0),
+            Angle::from_degrees(0.0),
+            Angle::from_degrees(92.0),
+        );
+        let warnings = screens.validate_angles();
+        let pairs: Vec<_> = warnings.iter().map(|w| (w.first, w.second)).collect();
+        assert_eq!(pairs, vec![(0, 1), (2, 3)]);
+        assert!((warnings[0].separation.into_radians().to_degrees() - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_grids() {
+        let grids = CmykScreens::standard().grids(60.0, 40.0, 7.0, 7.0, 0.0, 0.0);
+        for (grid, angle) in grids.into_iter().zip(CmykScreens::standard().angles()) {
+            let expected: Vec<_> =
+                GridPositionIterator::new(60.0, 40.0, 7.0, 7.0, 0.0, 0.0, angle).collect();
+            assert_eq!(grid.collect::<Vec<_>>(), expected);
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Contains the [`GridSpec`] type.
+
+use crate::{Angle, GridError, GridPositionIterator};
+
+/// A plain description of a grid, e.g. for storing grid configurations in files.
+///
+/// With the `serde` feature, the specification can be (de)serialized; the `json` feature
+/// additionally provides [`GridSpec::from_json`] and [`GridSpec::to_json`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridSpec {
+    /// The width of the grid.
+    pub width: f64,
+    /// The height of the grid.
+    pub height: f64,
+    /// The spacing of grid elements along the (rotated) X axis.
+    pub dx: f64,
+    /// The spacing of grid elements along the (rotated) Y axis.
+    pub dy: f64,
+    /// The X offset of the first grid element.
+    pub x0: f64,
+    /// The Y offset of the first grid element.
+    pub y0: f64,
+    /// The orientation of the grid in degrees.
+    pub angle: f64,
+}
+
+impl GridSpec {
+    /// Creates the grid described by the specification.
+    ///
+    /// ## Errors
+    /// Returns a [`GridError`] if the specification is invalid, see [`GridPositionIterator::try_new`].
+    pub fn build(&self) -> Result<GridPositionIterator, GridError> {
+        GridPositionIterator::try_new(
+            self.width,
+            self.height,
+            self.dx,
+            self.dy,
+            self.x0,
+            self.y0,
+            Angle::from_degrees(self.angle),
+        )
+    }
+
+    /// Determines a hash of the specification that is equal for specifications differing
+    /// only cosmetically, e.g. for caching rendered screens.
+    ///
+    /// The angle is taken modulo 360° and the offsets modulo the respective spacing, since
+    /// shifting the lattice by whole cells does not change the positions. The values are
+    /// quantized to a billionth of a unit before hashing to absorb rounding errors.
+    /// The hash is stable across platforms and compiler versions.
+    pub fn fingerprint(&self) -> u64 {
+        const SCALE: f64 = 1e9;
+        let quantize = |value: f64| (value * SCALE).round() as i64;
+        let offset = |offset: f64, spacing: f64| {
+            let quantized = quantize(offset.rem_euclid(spacing));
+            if quantized == quantize(spacing) {
+                0
+            } else {
+                quantized
+            }
+        };
+
+        let values = [
+            quantize(self.width),
+            quantize(self.height),
+            quantize(self.dx),
+            quantize(self.dy),
+            offset(self.x0, self.dx),
+            offset(self.y0, self.dy),
+            quantize(self.angle.rem_euclid(360.0)) % quantize(360.0),
+        ];
+
+        // 64-bit FNV-1a, which unlike the standard library's hasher is guaranteed to be stable.
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+            })
+    }
+
+    /// Parses a specification from a JSON object with the fields `width`, `height`, `dx`,
+    /// `dy`, `x0`, `y0` and `angle` (in degrees).
+    ///
+    /// The values are not validated; see [`GridSpec::build`].
+    ///
+    /// ## Errors
+    /// Returns [`GridError::MalformedSpec`] if the JSON is malformed or a field is missing.
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, GridError> {
+        serde_json::from_str(json).map_err(|_| GridError::MalformedSpec)
+    }
+
+    /// Serializes the specification into a JSON object, see [`GridSpec::from_json`].
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("specification is serializable")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> GridSpec {
+        GridSpec {
+            width: 100.0,
+            height: 80.0,
+            dx: 7.0,
+            dy: 5.0,
+            x0: 1.5,
+            y0: -2.0,
+            angle: 15.0,
+        }
+    }
+
+    #[test]
+    fn test_build() {
+        let expected: Vec<_> =
+            GridPositionIterator::new(100.0, 80.0, 7.0, 5.0, 1.5, -2.0, Angle::from_degrees(15.0))
+                .collect();
+        assert_eq!(spec().build().unwrap().collect::<Vec<_>>(), expected);
+
+        let invalid = GridSpec { dx: 0.0, ..spec() };
+        assert_eq!(invalid.build().err(), Some(GridError::NonPositiveSpacing));
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let fingerprint = spec().fingerprint();
+        let turned = GridSpec {
+            angle: 15.0 + 360.0,
+            ..spec()
+        };
+        let shifted = GridSpec {
+            x0: 1.5 + 7.0,
+            y0: -2.0 - 5.0,
+            ..spec()
+        };
+        assert_eq!(turned.fingerprint(), fingerprint);
+        assert_eq!(shifted.fingerprint(), fingerprint);
+
+        let different = GridSpec { x0: 2.5, ..spec() };
+        assert_ne!(different.fingerprint(), fingerprint);
+        let different = GridSpec {
+            angle: 16.0,
+            ..spec()
+        };
+        assert_ne!(different.fingerprint(), fingerprint);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_json_round_trip() {
+        let json = spec().to_json();
+        assert_eq!(GridSpec::from_json(&json), Ok(spec()));
+
+        let json =
+            r#"{"width": 100, "height": 80, "dx": 7, "dy": 5, "x0": 1.5, "y0": -2, "angle": 15}"#;
+        assert_eq!(GridSpec::from_json(json), Ok(spec()));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_malformed_json() {
+        assert_eq!(GridSpec::from_json("{"), Err(GridError::MalformedSpec));
+        assert_eq!(
+            GridSpec::from_json(r#"{"width": 100, "height": 80}"#),
+            Err(GridError::MalformedSpec)
+        );
+    }
+}
@@ -0,0 +1,288 @@
+//! Contains the [`GridStats`] type and other statistics of grid positions.
+
+use crate::inner::vector::Vector;
+use crate::GridPositionIterator;
+
+/// Summary statistics of the positions of a grid.
+///
+/// See [`GridPositionIterator::statistics`](crate::GridPositionIterator::statistics).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridStats {
+    /// The number of positions.
+    pub count: usize,
+    /// The minimum and maximum corner of the axis-aligned bounding box of the positions,
+    /// or `None` if there are no positions.
+    pub bounds: Option<(Vector, Vector)>,
+    /// The mean of the positions, or `None` if there are no positions.
+    pub centroid: Option<Vector>,
+    /// The smallest distance between consecutive positions of a row,
+    /// or `None` if no row contains more than one position.
+    pub min_spacing: Option<f64>,
+    /// The largest distance between consecutive positions of a row,
+    /// or `None` if no row contains more than one position.
+    pub max_spacing: Option<f64>,
+}
+
+impl GridPositionIterator {
+    /// Determines the centroid of the remaining grid positions without advancing the iterator,
+    /// or `None` if there are no remaining positions.
+    ///
+    /// Since the positions of each row are evenly spaced, the centroid is determined per row
+    /// rather than per position unless the grid is clipped or strided.
+    pub fn centroid(&self) -> Option<Vector> {
+        let mut count = 0;
+        let mut sum = Vector::default();
+        for (y, xs) in self.inner.remaining_rows() {
+            if self.is_filtered() {
+                for coord in xs.filter_map(|x| self.emit(Vector::new(x, y))) {
+                    count += 1;
+                    sum += Vector::new(coord.x, coord.y);
+                }
+            } else {
+                let n = xs.len();
+                count += n;
+                sum += Vector::new(xs.sum_remaining(), y * n as f64);
+            }
+        }
+
+        if count == 0 {
+            return None;
+        }
+
+        let centroid = sum / count as f64;
+        if self.is_filtered() {
+            Some(centroid)
+        } else {
+            // The centroid was determined in rotated space.
+            Some(self.unrotate(centroid).into_xy().into())
+        }
+    }
+
+    /// Determines the smallest distance between any two of the remaining grid positions,
+    /// or infinity if there are fewer than two positions.
+    ///
+    /// This is intended for sanity-checking grid setups. The positions are collected
+    /// and swept in order of their X coordinate, which takes O(n log n) time.
+    pub fn min_point_distance(self) -> f64 {
+        let mut points: Vec<_> = self.map(|coord| Vector::new(coord.x, coord.y)).collect();
+        points.sort_by(|a, b| a.x.partial_cmp(&b.x).expect("coordinates are finite"));
+
+        // The points within the current distance along X, ordered by their Y coordinate.
+        let mut active = std::collections::BTreeSet::new();
+        let mut best = f64::INFINITY;
+        let mut tail = 0;
+        for (index, point) in points.iter().enumerate() {
+            while tail < index && point.x - points[tail].x > best {
+                active.remove(&(ordered_bits(points[tail].y), tail));
+                tail += 1;
+            }
+
+            let from = (ordered_bits(point.y - best), 0);
+            let to = (ordered_bits(point.y + best), usize::MAX);
+            for &(_, other) in active.range(from..=to) {
+                best = best.min((*point - points[other]).norm());
+            }
+
+            active.insert((ordered_bits(point.y), index));
+        }
+
+        best
+    }
+
+    /// Consumes the iterator and summarizes the remaining positions in a single pass,
+    /// without collecting them.
+    ///
+    /// The spacing is measured between consecutive positions of the same row.
+    pub fn statistics(mut self) -> GridStats {
+        let mut count = 0;
+        let mut sum = Vector::new(0.0, 0.0);
+        let mut bounds: Option<(Vector, Vector)> = None;
+        let mut spacing: Option<(f64, f64)> = None;
+
+        while let Some(row) = self.next_row() {
+            let mut previous: Option<Vector> = None;
+            for coord in row {
+                let point = Vector::new(coord.x, coord.y);
+                count += 1;
+                sum += point;
+                bounds = Some(match bounds {
+                    None => (point, point),
+                    Some((min, max)) => (
+                        Vector::new(min.x.min(point.x), min.y.min(point.y)),
+                        Vector::new(max.x.max(point.x), max.y.max(point.y)),
+                    ),
+                });
+
+                if let Some(previous) = previous {
+                    let distance = (point - previous).norm();
+                    spacing = Some(match spacing {
+                        None => (distance, distance),
+                        Some((min, max)) => (min.min(distance), max.max(distance)),
+                    });
+                }
+                previous = Some(point);
+            }
+        }
+
+        GridStats {
+            count,
+            bounds,
+            centroid: (count > 0).then(|| sum / count as f64),
+            min_spacing: spacing.map(|(min, _)| min),
+            max_spacing: spacing.map(|(_, max)| max),
+        }
+    }
+
+    /// Determines the ratio of the exact number of remaining grid positions to the upper bound
+    /// reported by [`Iterator::size_hint`], e.g. to decide whether preallocating with the
+    /// upper bound is worthwhile.
+    ///
+    /// The upper bound assumes an unrotated grid with nodes on all edges of the rectangle;
+    /// if the spacing divides the size of the rectangle, the ratio of an unrotated grid is 1.0.
+    /// Rotated grids stay below that, since their rows rarely end exactly on the edges.
+    /// The over-estimate is about one row and one column of positions, so the ratio is
+    /// noticeably below 1.0 (e.g. 0.66 for six by four cells at 45°) for small grids only,
+    /// and approaches 1.0 for large grids regardless of the orientation.
+    pub fn estimate_ratio(&self) -> f64 {
+        self.exact_count() as f64 / self.estimate_max_grid_points() as f64
+    }
+
+    /// Scores how likely the grid is to alias with the pixel grid of the output, from `0.0`
+    /// (spacings halfway between two pixel counts) to `1.0` (spacings of whole pixel counts).
+    ///
+    /// If the spacing is close to an integer number of pixels, neighboring dots land on
+    /// nearly the same sub-pixel phase, which shows as strong moiré-like beating when the
+    /// dots are rasterized. The score is determined for both spacings independently and
+    /// the higher one is returned. It describes the spacing along the lattice axes, which
+    /// coincides with the pixel axes for unrotated grids only.
+    ///
+    /// ## Arguments
+    /// * `dpi` - The resolution of the output in dots (pixels) per inch, with the grid
+    ///   coordinates measured in inches. Use `1.0` if the grid coordinates already are
+    ///   pixels, e.g. for grids created by [`GridPositionIterator::from_lpi`].
+    pub fn aliasing_risk(&self, dpi: f64) -> f64 {
+        let risk = |spacing: f64| {
+            let pixels = spacing * dpi;
+            1.0 - 2.0 * (pixels - pixels.round()).abs()
+        };
+        risk(self.dx).max(risk(self.dy))
+    }
+}
+
+/// Maps a finite floating-point value to an integer with the same ordering.
+fn ordered_bits(value: f64) -> u64 {
+    let bits = value.to_bits();
+    if bits >> 63 == 1 {
+        !bits
+    } else {
+        bits | (1 << 63)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::grid;
+    use crate::Angle;
+
+    #[test]
+    fn test_centroid_of_centered_grid() {
+        let grid = GridPositionIterator::new(
+            49.0,
+            35.0,
+            7.0,
+            7.0,
+            0.0,
+            0.0,
+            Angle::<f64>::from_degrees(0.0),
+        );
+
+        let centroid = grid.centroid().expect("grid is not empty");
+        assert_vec_eq!(centroid, Vector::new(24.5, 17.5));
+    }
+
+    #[test]
+    fn test_centroid_of_offset_grid() {
+        let grid = GridPositionIterator::new(
+            100.0,
+            80.0,
+            7.0,
+            5.0,
+            1.0,
+            2.0,
+            Angle::<f64>::from_degrees(15.0),
+        );
+
+        let centroid = grid.centroid().expect("grid is not empty");
+        let points: Vec<_> = grid.collect();
+        let expected = Vector::new(
+            points.iter().map(|c| c.x).sum::<f64>() / points.len() as f64,
+            points.iter().map(|c| c.y).sum::<f64>() / points.len() as f64,
+        );
+        assert_vec_eq!(centroid, expected, 1e-6);
+    }
+
+    #[test]
+    fn test_min_point_distance() {
+        let grid = |dx: f64, dy: f64| {
+            GridPositionIterator::new(
+                200.0,
+                150.0,
+                dx,
+                dy,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(15.0),
+            )
+        };
+
+        assert!((grid(7.0, 7.0).min_point_distance() - 7.0).abs() < 1e-9);
+        assert!((grid(5.0, 9.0).min_point_distance() - 5.0).abs() < 1e-9);
+        assert!((grid(9.0, 5.0).min_point_distance() - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_statistics() {
+        let grid = || {
+            GridPositionIterator::new(
+                200.0,
+                150.0,
+                7.0,
+                7.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(15.0),
+            )
+        };
+
+        let stats = grid().statistics();
+        assert_eq!(stats.count, grid().exact_count());
+
+        let centroid = stats.centroid.unwrap();
+        assert!((centroid.x - 100.0).abs() < 1e-9);
+        assert!((centroid.y - 75.0).abs() < 1e-9);
+
+        let (min, max) = stats.bounds.unwrap();
+        assert!(min.x >= 0.0 && min.y >= 0.0 && max.x <= 200.0 && max.y <= 150.0);
+        assert!((stats.min_spacing.unwrap() - 7.0).abs() < 1e-9);
+        assert!((stats.max_spacing.unwrap() - 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_aliasing_risk() {
+        assert_eq!(grid(7.0, 0.0).aliasing_risk(1.0), 1.0);
+        assert_eq!(grid(7.5, 0.0).aliasing_risk(1.0), 0.0);
+        assert!(grid(7.45, 0.0).aliasing_risk(1.0) < 0.2);
+        assert!(grid(7.02, 0.0).aliasing_risk(1.0) > 0.9);
+
+        // A spacing of 0.05 inches is 15 pixels at 300 dpi, but 7.5 pixels at 150 dpi.
+        assert!(grid(0.05, 0.0).aliasing_risk(300.0) > 0.99);
+        assert!(grid(0.05, 0.0).aliasing_risk(150.0) < 0.01);
+    }
+
+    #[test]
+    fn test_estimate_ratio() {
+        assert_eq!(grid(10.0, 0.0).estimate_ratio(), 1.0);
+        assert!(grid(10.0, 45.0).estimate_ratio() < 1.0);
+    }
+}
@@ -0,0 +1,53 @@
+//! Contains the conversion of grids into SVG documents.
+
+use crate::GridPositionIterator;
+use std::fmt::Write;
+
+impl GridPositionIterator {
+    /// Renders filled dots at the remaining grid positions into a standalone SVG document,
+    /// e.g. for visually inspecting a grid or producing print proofs.
+    ///
+    /// The document uses a view box of the specified size with one unit per pixel,
+    /// such that the positions are drawn at their coordinates. Dots extending beyond
+    /// the document are cropped by the viewer.
+    ///
+    /// ## Arguments
+    /// * `width` - The width of the document in pixels.
+    /// * `height` - The height of the document in pixels.
+    /// * `dot_radius` - The radius of the dots in pixels.
+    pub fn to_svg(self, width: u32, height: u32, dot_radius: f64) -> String {
+        let mut svg = String::new();
+        // Writing into a string cannot fail.
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        );
+        for coord in self {
+            let _ = writeln!(
+                svg,
+                r#"  <circle cx="{}" cy="{}" r="{}"/>"#,
+                coord.x, coord.y, dot_radius
+            );
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::grid;
+
+    #[test]
+    fn test_to_svg() {
+        let svg = grid(7.0, 15.0).to_svg(60, 40, 2.0);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains(r#"width="60""#));
+        assert!(svg.contains(r#"height="40""#));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(
+            svg.matches("<circle").count(),
+            grid(7.0, 15.0).exact_count()
+        );
+    }
+}
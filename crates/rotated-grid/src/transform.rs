@@ -0,0 +1,205 @@
+//! Contains transformations of the emitted grid positions.
+
+use crate::inner::vector::Vector;
+use crate::{Angle, GridCoord, GridPositionIterator};
+
+impl GridPositionIterator {
+    /// Applies an affine transformation to each emitted position.
+    ///
+    /// The transformation is given as a 2×3 matrix in row-major order, i.e.
+    /// `[m00, m01, m02, m10, m11, m12]`, such that
+    /// `x' = m00·x + m01·y + m02` and `y' = m10·x + m11·y + m12`.
+    pub fn transformed(self, m: [f64; 6]) -> impl Iterator<Item = GridCoord> {
+        self.map(move |GridCoord { x, y }| {
+            GridCoord::new(m[0] * x + m[1] * y + m[2], m[3] * x + m[4] * y + m[5])
+        })
+    }
+
+    /// Expresses each emitted position relative to the rectangle, i.e. in normalized
+    /// `[0, 1]` coordinates, e.g. for resolution-independent storage.
+    ///
+    /// The X and Y coordinates are measured from the top-left corner of the rectangle and
+    /// divided by its width and height, respectively. Positions on the edges of the rectangle
+    /// may exceed the unit range by rounding errors.
+    pub fn normalized_coords(self) -> impl Iterator<Item = GridCoord> {
+        let (origin, width, height) = (self.origin, self.width, self.height);
+        self.map(move |GridCoord { x, y }| {
+            GridCoord::new((x - origin.x) / width, (y - origin.y) / height)
+        })
+    }
+
+    /// Emits each position in polar form relative to the center of the rectangle, i.e. as its
+    /// distance from the center and the direction of the offset from the center as given by
+    /// `atan2`, in range -PI..=PI, e.g. for radial analysis.
+    pub fn polar(self) -> impl Iterator<Item = (f64, Angle<f64>)> {
+        let center = *self.inner.center();
+        self.map(move |coord| {
+            let offset = Vector::new(coord.x - center.x, coord.y - center.y);
+            (offset.norm(), Angle::from_radians(offset.y.atan2(offset.x)))
+        })
+    }
+
+    /// Emits each position as its offset from the previously emitted position, e.g. for
+    /// delta-encoded plotter output. The first position is emitted as is, i.e. as its
+    /// offset from the origin of the coordinate system.
+    ///
+    /// Summing up the offsets reconstructs the positions, up to rounding errors.
+    pub fn deltas(self) -> impl Iterator<Item = Vector> {
+        self.scan(Vector::default(), |previous, coord| {
+            let current = Vector::new(coord.x, coord.y);
+            let delta = current - *previous;
+            *previous = current;
+            Some(delta)
+        })
+    }
+
+    /// Rotates each emitted position around a pivot into a different output frame,
+    /// without changing the lattice itself.
+    ///
+    /// The rotation is applied in the same sense as the orientation of the grid, such that
+    /// rotating the frame by `-alpha` around the center of the rectangle yields the positions
+    /// of the unrotated lattice. To instead apply an arbitrary affine transformation,
+    /// see [`GridPositionIterator::transformed`].
+    pub fn in_frame(
+        self,
+        frame_rotation: Angle<f64>,
+        pivot: Vector,
+    ) -> impl Iterator<Item = GridCoord> {
+        let (sin, cos) = frame_rotation.sin_cos();
+        self.map(move |coord| {
+            let point = Vector::new(coord.x, coord.y).rotate_around_with(&pivot, -sin, cos);
+            GridCoord::new(point.x, point.y)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::grid;
+    use crate::Angle;
+
+    #[test]
+    fn test_transformed() {
+        let transformed: Vec<_> = grid(7.0, 15.0)
+            .transformed([2.0, 0.0, 10.0, 0.0, 2.0, 20.0])
+            .collect();
+        let expected: Vec<_> = grid(7.0, 15.0)
+            .map(|c| GridCoord::new(2.0 * c.x + 10.0, 2.0 * c.y + 20.0))
+            .collect();
+        assert!(!transformed.is_empty());
+        assert_eq!(transformed, expected);
+    }
+
+    #[test]
+    fn test_normalized_coords() {
+        let grid = || {
+            GridPositionIterator::new_at(
+                Vector::new(-20.0, 10.0),
+                50.0,
+                30.0,
+                5.0,
+                5.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(0.0),
+            )
+        };
+
+        let normalized: Vec<_> = grid().normalized_coords().collect();
+        assert_eq!(normalized.len(), grid().count());
+        for coord in &normalized {
+            assert!(-1e-9 <= coord.x && coord.x <= 1.0 + 1e-9);
+            assert!(-1e-9 <= coord.y && coord.y <= 1.0 + 1e-9);
+        }
+
+        // Nodes on the edges of the rectangle map to the edges of the unit square.
+        assert!(normalized
+            .iter()
+            .any(|c| c.approx_eq(&GridCoord::new(0.0, 0.0), 1e-9)));
+        assert!(normalized
+            .iter()
+            .any(|c| c.approx_eq(&GridCoord::new(1.0, 1.0), 1e-9)));
+    }
+
+    #[test]
+    fn test_polar() {
+        let grid = || {
+            GridPositionIterator::new(
+                60.0,
+                40.0,
+                7.0,
+                5.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(20.0),
+            )
+        };
+
+        let polar: Vec<_> = grid().polar().collect();
+        assert_eq!(polar.len(), grid().count());
+        let min_radius = polar.iter().map(|(r, _)| *r).fold(f64::INFINITY, f64::min);
+        assert!(min_radius < 1e-9);
+
+        // The center-anchored grid is point symmetric about the center.
+        let tolerance = Angle::from_radians(1e-9);
+        for (radius, angle) in polar.iter().filter(|(r, _)| *r > 1e-9) {
+            let opposite = Angle::from_radians(angle.into_radians() + std::f64::consts::PI);
+            assert!(polar
+                .iter()
+                .any(|(r, a)| (r - radius).abs() < 1e-9 && a.approx_eq(&opposite, tolerance)));
+        }
+    }
+
+    #[test]
+    fn test_deltas() {
+        let grid = || {
+            GridPositionIterator::new_at(
+                Vector::new(10.0, 20.0),
+                60.0,
+                40.0,
+                7.0,
+                7.0,
+                0.0,
+                0.0,
+                Angle::<f64>::from_degrees(30.0),
+            )
+        };
+
+        let absolute: Vec<_> = grid().collect();
+        let deltas: Vec<_> = grid().deltas().collect();
+        assert_eq!(deltas.len(), absolute.len());
+        assert_eq!(deltas[0], Vector::new(absolute[0].x, absolute[0].y));
+
+        let mut sum = Vector::default();
+        for (delta, coord) in deltas.iter().zip(&absolute) {
+            sum += *delta;
+            assert!(sum.approx_eq(&Vector::new(coord.x, coord.y), 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_in_frame() {
+        let alpha = Angle::<f64>::from_degrees(30.0);
+        let grid = || GridPositionIterator::new(60.0, 40.0, 7.0, 5.0, 1.0, 2.0, alpha);
+        let center = Vector::new(30.0, 20.0);
+
+        // In the frame rotated by -alpha, the nodes form the unrotated lattice around the center.
+        let coords: Vec<_> = grid().in_frame(-alpha, center).collect();
+        assert_eq!(coords.len(), grid().count());
+        for coord in coords {
+            let column = (coord.x - center.x - 1.0) / 7.0;
+            let row = (coord.y - center.y - 2.0) / 5.0;
+            assert!((column - column.round()).abs() < 1e-9);
+            assert!((row - row.round()).abs() < 1e-9);
+        }
+
+        // Rotating the frame back restores the positions.
+        let restored = grid()
+            .in_frame(-alpha, center)
+            .map(|c| Vector::new(c.x, c.y).rotate_around(&center, -alpha));
+        for (a, b) in restored.zip(grid()) {
+            assert!(a.approx_eq(&Vector::new(b.x, b.y), 1e-9));
+        }
+    }
+}
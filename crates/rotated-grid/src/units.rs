@@ -0,0 +1,31 @@
+//! Contains conversions between print units and pixel spacings.
+
+/// Converts a screen frequency in lines per inch into the spacing of grid elements in pixels.
+///
+/// ## Arguments
+/// * `lpi` - The screen frequency in lines per inch. Must be positive.
+/// * `dpi` - The resolution of the output in dots (pixels) per inch.
+pub fn lpi_to_spacing(lpi: f64, dpi: f64) -> f64 {
+    dpi / lpi
+}
+
+/// Converts the spacing of grid elements in pixels into a screen frequency in lines per inch.
+///
+/// ## Arguments
+/// * `spacing_px` - The spacing of grid elements in pixels. Must be positive.
+/// * `dpi` - The resolution of the output in dots (pixels) per inch.
+pub fn spacing_to_lpi(spacing_px: f64, dpi: f64) -> f64 {
+    dpi / spacing_px
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(lpi_to_spacing(150.0, 300.0), 2.0);
+        assert_eq!(spacing_to_lpi(2.0, 300.0), 150.0);
+        assert_eq!(spacing_to_lpi(lpi_to_spacing(133.0, 2400.0), 2400.0), 133.0);
+    }
+}
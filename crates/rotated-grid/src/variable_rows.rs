@@ -0,0 +1,105 @@
+//! Contains the [`VariableRowGrid`] type.
+
+use crate::inner::optimal_iterator::OptimalXIterator;
+use crate::inner::vector::Vector;
+use crate::{GridCoord, GridPositionIterator};
+
+/// An iterator for the positions on a rotated grid whose row spacing varies with the row.
+///
+/// See [`GridPositionIterator::with_row_spacing`].
+pub struct VariableRowGrid<F> {
+    grid: GridPositionIterator,
+    spacing: F,
+    /// The Y coordinate of the next row in rotated grid space.
+    y: f64,
+    /// The current row and its Y coordinate in rotated grid space.
+    row: Option<(f64, OptimalXIterator)>,
+}
+
+impl<F> VariableRowGrid<F>
+where
+    F: Fn(f64) -> f64,
+{
+    pub(crate) fn new(grid: GridPositionIterator, spacing: F) -> Self {
+        let y = grid.inner.first_row_y();
+        Self {
+            grid,
+            spacing,
+            y,
+            row: None,
+        }
+    }
+}
+
+impl<F> Iterator for VariableRowGrid<F>
+where
+    F: Fn(f64) -> f64,
+{
+    type Item = GridCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((y, xs)) = self.row.as_mut() {
+                let y = *y;
+                for x in xs.by_ref() {
+                    if let Some(coord) = self.grid.emit(Vector::new(x, y)) {
+                        return Some(coord);
+                    }
+                }
+                self.row = None;
+            }
+
+            if self.grid.inner.is_below_last_row(self.y) {
+                return None;
+            }
+
+            let y = self.y;
+            let spacing = (self.spacing)(y);
+            assert!(spacing > 0.0, "row spacing must be positive");
+            self.y += spacing;
+            self.row = self.grid.inner.row_at(y, false).map(|xs| (y, xs));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Angle, GridPositionIterator};
+
+    fn grid(degrees: f64) -> GridPositionIterator {
+        GridPositionIterator::new(
+            60.0,
+            80.0,
+            5.0,
+            4.0,
+            0.5,
+            1.0,
+            Angle::<f64>::from_degrees(degrees),
+        )
+    }
+
+    #[test]
+    fn test_constant_spacing() {
+        for degrees in [0.0, 30.0] {
+            let variable: Vec<_> = grid(degrees).with_row_spacing(|_| 4.0).collect();
+            let expected: Vec<_> = grid(degrees).collect();
+            assert_eq!(variable, expected);
+        }
+    }
+
+    #[test]
+    fn test_widening_rows() {
+        let coords: Vec<_> = grid(0.0).with_row_spacing(|y| 2.0 + 0.1 * y).collect();
+        let mut rows: Vec<f64> = Vec::new();
+        for coord in &coords {
+            if rows.last() != Some(&coord.y) {
+                rows.push(coord.y);
+            }
+        }
+
+        assert!(rows.len() > 5);
+        let gaps: Vec<_> = rows.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        assert!(gaps.windows(2).all(|pair| pair[1] > pair[0]));
+        assert!(coords.iter().all(|c| c.y <= 80.0));
+    }
+}
@@ -0,0 +1,97 @@
+//! Contains the [`SpacingWedge`] type.
+
+use crate::inner::vector::Vector;
+use crate::{Angle, GridPositionIterator};
+
+/// An iterator for a series of grids with different spacings laid side by side,
+/// e.g. for dot-gain calibration wedges.
+///
+/// The total width is split into bands of equal width, one per spacing, in order from left to
+/// right. Each band spans the total height and holds a grid using the band's spacing along both axes.
+pub struct SpacingWedge {
+    band_width: f64,
+    height: f64,
+    spacings: Vec<f64>,
+    alpha: Angle<f64>,
+    index: usize,
+}
+
+impl SpacingWedge {
+    /// Creates a new wedge.
+    ///
+    /// ## Arguments
+    /// * `width` - The total width of the wedge. Must be positive.
+    /// * `height` - The height of the wedge. Must be positive.
+    /// * `spacings` - The spacings of the grids, in order from left to right. Must be positive.
+    /// * `alpha` - The orientation of the grids. Must be in range 0..90°.
+    pub fn new(width: f64, height: f64, spacings: &[f64], alpha: Angle<f64>) -> Self {
+        assert!(width > 0.0);
+        assert!(height > 0.0);
+        assert!(spacings.iter().all(|&spacing| spacing > 0.0));
+
+        Self {
+            band_width: width / spacings.len().max(1) as f64,
+            height,
+            spacings: spacings.to_vec(),
+            alpha,
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for SpacingWedge {
+    type Item = (f64, GridPositionIterator);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let spacing = *self.spacings.get(self.index)?;
+        let origin = Vector::new(self.index as f64 * self.band_width, 0.0);
+        self.index += 1;
+
+        let grid = GridPositionIterator::new_at(
+            origin,
+            self.band_width,
+            self.height,
+            spacing,
+            spacing,
+            0.0,
+            0.0,
+            self.alpha,
+        );
+        Some((spacing, grid))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.spacings.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for SpacingWedge {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bands() {
+        let spacings = [8.0, 6.0, 4.0, 2.0];
+        let wedge = SpacingWedge::new(200.0, 50.0, &spacings, Angle::from_degrees(15.0));
+        assert_eq!(wedge.len(), 4);
+
+        for (index, (spacing, grid)) in wedge.enumerate() {
+            assert_eq!(spacing, spacings[index]);
+
+            let (u, v) = grid.lattice_vectors();
+            assert!((u.norm() - spacing).abs() < 1e-9);
+            assert!((v.norm() - spacing).abs() < 1e-9);
+
+            let (min_x, max_x) = (index as f64 * 50.0, (index + 1) as f64 * 50.0);
+            let coords: Vec<_> = grid.collect();
+            assert!(!coords.is_empty());
+            for coord in coords {
+                assert!(coord.x >= min_x - 1e-9 && coord.x <= max_x + 1e-9);
+                assert!(coord.y >= -1e-9 && coord.y <= 50.0 + 1e-9);
+            }
+        }
+    }
+}